@@ -1,11 +1,8 @@
 use std::time::Instant;
 
-use veracity_core::enums::distance_metrics::DistanceMetrics;
-use veracity_core::neighbors::k_neighbors_classifier::{KNeighborsClassifier, KNeighborsClassifierSettings};
 use veracity_core::base::regressor_base::RegressorBase;
 use veracity_core::neighbors::k_neighbors_regressor::{KNeighborsRegressor, KNeighborsRegressorSettings};
-use veracity_core::neighbors::k_neighbors_weights::KNeighborsWeights;
-use veracity_data::{data_loader::{csv_loader::{CSVLoader, CSVLoaderSettings}, DataLoader}, data_matrix::DataMatrix, data_vector::DataVector};
+use veracity_data::{data_loader::{csv_loader::{CSVLoader, CSVLoaderSettings}, DataLoader}, data_matrix::DataMatrix, data_vector::DataVector, enums::cast_mode::CastMode};
 
 #[tokio::main]
 async fn main() {
@@ -17,12 +14,15 @@ async fn main() {
     let data_loader: CSVLoader = CSVLoader::new(csv_loader_settings);
 
     let training_data: DataMatrix = data_loader.load_from("/Users/zai/data/training.csv").await.unwrap();
-    let x_train: DataMatrix = training_data.exclude_columns(vec!["class", "b4"]).unwrap();
-    let y_train: DataVector = training_data.get_column("b4").unwrap();
+    let feature_columns: Vec<String> = training_data.column_names().into_iter().filter(|name| name != "class" && name != "b4").collect();
+    let features: Vec<&str> = feature_columns.iter().map(String::as_str).collect();
+
+    let x_train: DataMatrix = training_data.get_columns(features.clone()).unwrap().cast::<f64>(features.clone(), CastMode::Checked).unwrap();
+    let y_train: DataVector = training_data.get_column("b4").unwrap().cast::<f64>(CastMode::Checked).unwrap();
 
     let testing_data: DataMatrix = data_loader.load_from("/Users/zai/data/testing.csv").await.unwrap();
-    let x_test: DataMatrix = testing_data.exclude_columns(vec!["class", "b4"]).unwrap();
-    let y_test: DataVector = testing_data.get_column("b4").unwrap();
+    let x_test: DataMatrix = testing_data.get_columns(features.clone()).unwrap().cast::<f64>(features, CastMode::Checked).unwrap();
+    let y_test: DataVector = testing_data.get_column("b4").unwrap().cast::<f64>(CastMode::Checked).unwrap();
 
     let regressor_settings: KNeighborsRegressorSettings = KNeighborsRegressorSettings {
         ..Default::default()
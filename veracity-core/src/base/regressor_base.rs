@@ -20,5 +20,7 @@ pub trait RegressorBase<T: Num + Copy, D: Dimension, U> {
 
     fn score(&self, x: &DataMatrix, y: &DataVector) -> Result<f64, VeracityError>;
 
+    fn feature_names(&self) -> Option<&[String]>;
+
     fn add_settings<S: SettingsBase + 'static>(&mut self, settings: S) -> Result<(), VeracityError>;
 }
\ No newline at end of file
@@ -9,7 +9,7 @@ where
 {
     assert_eq!(y_pred.len(), y_actual.len(), "Arrays must be the same length.");
 
-    if y_actual.len() == 0 {
+    if y_actual.is_empty() {
         return 0.0;
     }
 
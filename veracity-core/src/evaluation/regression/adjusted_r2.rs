@@ -20,7 +20,7 @@ where
         "Number of samples must be greater than number of features + 1"
     );
 
-    let y_mean: f64 = y_actual.iter().map(|y: &U| (*y).into()).sum::<f64>() / (n as f64);
+    let y_mean: f64 = y_actual.iter().map(|y: &U| (*y).into()).sum::<f64>() / n;
 
     let ss_total: f64 = y_actual
         .iter()
@@ -42,7 +42,7 @@ where
 
     let r2: f64 = 1.0 - (ss_res / ss_total);
 
-    1.0 - (1.0 - r2) * ((n - 1.0) as f64) / ((n - num_features - 1.0) as f64)
+    1.0 - (1.0 - r2) * (n - 1.0) / (n - num_features - 1.0)
 }
 
 pub fn adjusted_r2<T, U>(y_pred: &DataVector, y_actual: &DataVector, num_features: &usize) -> f64
@@ -2,10 +2,10 @@ use std::{any::Any, cmp::Ordering, collections::BTreeMap, iter::Sum};
 
 use ndarray::{Array1, Array2, ArrayBase, Axis, Ix2, OwnedRepr};
 use num_traits::{Float, Num, ToPrimitive};
-use veracity_data::{data_matrix::DataMatrix, data_vector::DataVector};
+use veracity_data::{column_data::ColumnType, data_matrix::DataMatrix, data_vector::DataVector};
 use veracity_types::errors::VeracityError;
 
-use crate::{base::{classifier_base::ClassifierBase, settings_base::SettingsBase}, enums::distance_metrics::DistanceMetrics, utility::distance::{find_distance_cosine, find_distance_euclidean, find_distance_manhatten, find_distance_minkowski, find_distance_nan_euclidean}};
+use crate::{base::{classifier_base::ClassifierBase, settings_base::SettingsBase}, enums::distance_metrics::DistanceMetrics, utility::{distance::{find_distance_cosine, find_distance_euclidean, find_distance_manhatten, find_distance_minkowski, find_distance_nan_euclidean}, features::features_to_ndarray}};
 
 use super::k_neighbors_weights::KNeighborsWeights;
 
@@ -32,39 +32,56 @@ impl Default for KNeighborsClassifierSettings {
     }
 }
 
-pub struct KNeighborsClassifier<T: Num + Copy, U> {
+pub struct KNeighborsClassifier<T: Num + Copy, U: ColumnType> {
     x: Option<Array2<T>>,
     y: Option<Array1<U>>,
+    feature_names: Option<Vec<String>>,
+    target_categories: Option<Vec<String>>,
     settings: KNeighborsClassifierSettings
 }
 
-impl<T, U> KNeighborsClassifier<T, U> where T: Num + Copy {
+impl<T, U> KNeighborsClassifier<T, U> where T: Num + Copy, U: ColumnType {
     pub fn new() -> Self {
         KNeighborsClassifier {
             x: None,
             y: None,
+            feature_names: None,
+            target_categories: None,
             settings: KNeighborsClassifierSettings::default()
         }
     }
 }
 
-impl<U> KNeighborsClassifier<f32, U> {
+impl<T, U> Default for KNeighborsClassifier<T, U> where T: Num + Copy, U: ColumnType {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<U: ColumnType> KNeighborsClassifier<f32, U> {
     pub fn check_for_nan(&self, x: &ArrayBase<OwnedRepr<f32>, Ix2>) -> bool {
         x.iter().any(|&val| val.is_nan())
     }
 }
 
-impl<T: Float + Sum + Send + Sync + 'static, U: std::fmt::Debug + Clone + Ord + Send + Sync + 'static> ClassifierBase<T, Ix2, U> for KNeighborsClassifier<T, U> where T: Num + ToPrimitive + Copy, U: Eq + Clone {
+impl<T: Float + Sum + Send + Sync + 'static, U: ColumnType + std::fmt::Debug + Ord> ClassifierBase<T, Ix2, U> for KNeighborsClassifier<T, U> where T: Num + ToPrimitive + Copy, U: Eq + Clone {
     fn _fit(&mut self, x: &Array2<T>, y: &Array1<U>) -> Result<(), VeracityError> {
         self.x = Some(x.to_owned());
         self.y = Some(y.to_owned());
+        self.feature_names = None;
+        self.target_categories = None;
         Ok(())
     }
 
     fn fit(&mut self, x: &DataMatrix, y: &DataVector) -> Result<(), VeracityError> {
-        let x: Array2<T> = x.to_ndarray()?;
-        let y: Array1<U> = y.to_ndarray()?;
-        self._fit(&x, &y)
+        let x_array: Array2<T> = x.to_ndarray()?;
+        let y_array: Array1<U> = y.to_ndarray()?;
+        self._fit(&x_array, &y_array)?;
+        self.feature_names = Some(x.column_names());
+        // Categorical targets are fitted on their decoded labels and predictions are
+        // encoded back against the same categories.
+        self.target_categories = y.categories().map(<[String]>::to_vec);
+        Ok(())
     }
 
     fn _predict(&self, x_test: &Array2<T>) -> Result<Array1<U>, VeracityError> {
@@ -120,8 +137,11 @@ impl<T: Float + Sum + Send + Sync + 'static, U: std::fmt::Debug + Clone + Ord +
     }
 
     fn predict(&self, x: &DataMatrix) -> Result<DataVector, VeracityError> {
-        let result: Array1<U> = self._predict(&x.to_ndarray()?)?;
+        let result: Array1<U> = self._predict(&features_to_ndarray(x, self.feature_names())?)?;
         let mut data_vector: DataVector = DataVector::from_ndarray(result)?;
+        if let Some(categories) = &self.target_categories {
+            data_vector = data_vector.to_categorical_with(categories.clone())?;
+        }
         data_vector.add_label("predictions");
         Ok(data_vector)
     }
@@ -167,7 +187,7 @@ impl<T: Float + Sum + Send + Sync + 'static, U: std::fmt::Debug + Clone + Ord +
     }
 
     fn predict_proba(&self, x: &DataMatrix) -> Result<Vec<DataMatrix>, VeracityError> {
-        let result:Vec<BTreeMap<U, f64>>  = self._predict_proba(&x.to_ndarray()?)?;
+        let result:Vec<BTreeMap<U, f64>>  = self._predict_proba(&features_to_ndarray(x, self.feature_names())?)?;
         Ok(result
             .into_iter()
             .map(|probs: BTreeMap<U, f64>| {
@@ -199,7 +219,11 @@ impl<T: Float + Sum + Send + Sync + 'static, U: std::fmt::Debug + Clone + Ord +
     }
 
     fn score(&self, x: &DataMatrix, y: &DataVector) -> Result<f64, VeracityError> {
-        Ok(self._score(&x.to_ndarray()?, &y.to_ndarray()?)?)
+        self._score(&features_to_ndarray(x, self.feature_names())?, &y.to_ndarray()?)
+    }
+
+    fn feature_names(&self) -> Option<&[String]> {
+        self.feature_names.as_deref()
     }
 
     fn add_settings<S: SettingsBase + 'static>(&mut self, settings: S) -> Result<(), VeracityError> {
@@ -212,4 +236,34 @@ impl<T: Float + Sum + Send + Sync + 'static, U: std::fmt::Debug + Clone + Ord +
             Err(VeracityError::Classifier("Invalid settings type passed to KNeighborsClassifier".to_string()))
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use veracity_types::dtype::DType;
+
+    use super::*;
+
+    fn column<V: ColumnType>(label: &str, values: Vec<V>) -> DataVector {
+        let mut column: DataVector = DataVector::from_vec(values).unwrap();
+        column.add_label(label);
+        column
+    }
+
+    #[test]
+    fn categorical_targets_predict_categorical_labels() {
+        let x: DataMatrix = DataMatrix::from_vec(vec![column("x", vec![0.0, 0.1, 5.0, 5.1])]).unwrap();
+        let y: DataVector = column("pet", ["dog", "dog", "cat", "cat"].map(String::from).to_vec()).to_categorical().unwrap();
+
+        let mut classifier: KNeighborsClassifier<f64, String> = KNeighborsClassifier::new();
+        classifier.add_settings(KNeighborsClassifierSettings { k_neighbors: 1, ..Default::default() }).unwrap();
+        classifier.fit(&x, &y).unwrap();
+
+        let test: DataMatrix = DataMatrix::from_vec(vec![column("x", vec![5.05, 0.05])]).unwrap();
+        let predictions: DataVector = classifier.predict(&test).unwrap();
+
+        assert_eq!(predictions.dtype(), DType::Categorical);
+        assert_eq!(predictions.categories(), y.categories());
+        assert_eq!(predictions.to_options::<String>().unwrap(), vec![Some("cat".to_string()), Some("dog".to_string())]);
+    }
+}
@@ -2,10 +2,10 @@ use std::{any::Any, iter::Sum};
 
 use ndarray::{Array1, Array2, Ix2};
 use num_traits::{Float, Num, ToPrimitive};
-use veracity_data::{data_matrix::DataMatrix, data_vector::DataVector};
+use veracity_data::{column_data::ColumnType, data_matrix::DataMatrix, data_vector::DataVector};
 use veracity_types::errors::VeracityError;
 
-use crate::{base::{regressor_base::RegressorBase, settings_base::SettingsBase}, enums::distance_metrics::DistanceMetrics, utility::distance::{find_distance_cosine, find_distance_euclidean, find_distance_manhatten, find_distance_minkowski, find_distance_nan_euclidean}};
+use crate::{base::{regressor_base::RegressorBase, settings_base::SettingsBase}, enums::distance_metrics::DistanceMetrics, utility::{distance::{find_distance_cosine, find_distance_euclidean, find_distance_manhatten, find_distance_minkowski, find_distance_nan_euclidean}, features::features_to_ndarray}};
 
 use super::k_neighbors_weights::KNeighborsWeights;
 
@@ -33,6 +33,7 @@ impl Default for KNeighborsRegressorSettings {
 pub struct KNeighborsRegressor<T: Num + Copy, U> {
     x: Option<Array2<T>>,
     y: Option<Array1<U>>,
+    feature_names: Option<Vec<String>>,
     settings: KNeighborsRegressorSettings
 }
 
@@ -41,23 +42,33 @@ impl<T, U> KNeighborsRegressor<T, U> where T: Num + Copy {
         KNeighborsRegressor {
             x: None,
             y: None,
+            feature_names: None,
             settings: KNeighborsRegressorSettings::default()
         }
     }
 }
 
+impl<T, U> Default for KNeighborsRegressor<T, U> where T: Num + Copy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 
-impl<T: Copy + Float + Sync + Send + ToPrimitive + 'static, U: Clone + Sync + Send + Float + Sum + 'static> RegressorBase<T, Ix2, U> for KNeighborsRegressor<T, U> {
+impl<T: Copy + Float + Sync + Send + ToPrimitive + 'static, U: ColumnType + Float + Sum> RegressorBase<T, Ix2, U> for KNeighborsRegressor<T, U> {
     fn _fit(&mut self, x: &Array2<T>, y: &Array1<U>) -> Result<(), VeracityError> {
         self.x = Some(x.to_owned());
         self.y = Some(y.to_owned());
+        self.feature_names = None;
         Ok(())
     }
 
     fn fit(&mut self, x: &DataMatrix, y: &DataVector) -> Result<(), VeracityError> {
-        let x: Array2<T> = x.to_ndarray()?;
+        let x_array: Array2<T> = x.to_ndarray()?;
         let y: Array1<U> = y.to_ndarray()?;
-        self._fit(&x, &y)
+        self._fit(&x_array, &y)?;
+        self.feature_names = Some(x.column_names());
+        Ok(())
     }
 
     fn _predict(&self, x: &Array2<T>) -> Result<Array1<U>, VeracityError> {
@@ -111,7 +122,7 @@ impl<T: Copy + Float + Sync + Send + ToPrimitive + 'static, U: Clone + Sync + Se
     }
 
     fn predict(&self, x: &DataMatrix) -> Result<DataVector, VeracityError> {
-        let result: Array1<U> = self._predict(&x.to_ndarray()?)?;
+        let result: Array1<U> = self._predict(&features_to_ndarray(x, self.feature_names())?)?;
         let mut data_vector: DataVector = DataVector::from_ndarray(result)?;
         data_vector.add_label("predictions");
         Ok(data_vector)
@@ -139,7 +150,11 @@ impl<T: Copy + Float + Sync + Send + ToPrimitive + 'static, U: Clone + Sync + Se
     }
 
     fn score(&self, x: &veracity_data::data_matrix::DataMatrix, y: &veracity_data::data_vector::DataVector) -> Result<f64, veracity_types::errors::VeracityError> {
-        Ok(self._score(&x.to_ndarray()?, &y.to_ndarray()?)?)
+        self._score(&features_to_ndarray(x, self.feature_names())?, &y.to_ndarray()?)
+    }
+
+    fn feature_names(&self) -> Option<&[String]> {
+        self.feature_names.as_deref()
     }
 
     fn add_settings<S: SettingsBase + 'static>(&mut self, settings: S) -> Result<(), veracity_types::errors::VeracityError> {
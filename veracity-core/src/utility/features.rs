@@ -0,0 +1,12 @@
+use ndarray::Array2;
+use veracity_data::data_matrix::DataMatrix;
+use veracity_types::errors::VeracityError;
+
+// Converts x to a feature array laid out in the order the model was fitted with. Models
+// fitted directly on an ndarray have no feature names, so x is used as is.
+pub fn features_to_ndarray<T: Clone + Send + Sync + 'static>(x: &DataMatrix, feature_names: Option<&[String]>) -> Result<Array2<T>, VeracityError> {
+    match feature_names {
+        Some(names) => Ok(x.align_columns(names)?.to_ndarray()?),
+        None => Ok(x.to_ndarray()?)
+    }
+}
@@ -1 +1,2 @@
-pub mod distance;
\ No newline at end of file
+pub mod distance;
+pub mod features;
\ No newline at end of file
@@ -0,0 +1,166 @@
+use veracity_types::dtype::DType;
+
+use crate::{column_data::{ColumnData, ColumnType}, data_matrix::DataMatrix, data_vector::DataVector, enums::{cast_mode::CastMode, error_types::DataLoaderError}};
+
+// Integers up to 2^53 in magnitude are exactly representable as f64.
+const MAX_EXACT_F64: u64 = 1 << 53;
+
+enum Value {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String)
+}
+
+fn source_value(data: &ColumnData, row: usize) -> Option<Value> {
+    match data {
+        ColumnData::Bool(vec) => vec.get(row).copied().map(Value::Bool),
+        ColumnData::I32(_) | ColumnData::I64(_) => data.value_as_i64(row).map(Value::Int),
+        ColumnData::F32(_) | ColumnData::F64(_) => data.value_as_f64(row).map(Value::Float),
+        ColumnData::Utf8(_) | ColumnData::Categorical(_) => data.display_value(row).map(Value::Text),
+        ColumnData::Datetime(_) | ColumnData::Duration(_) => None
+    }
+}
+
+fn to_i64(value: &Value, mode: CastMode) -> Option<i64> {
+    match (value, mode) {
+        (Value::Bool(v), _) => Some(*v as i64),
+        (Value::Int(v), _) => Some(*v),
+        (Value::Float(v), CastMode::Checked) => (v.fract() == 0.0 && *v >= i64::MIN as f64 && *v < i64::MAX as f64).then_some(*v as i64),
+        (Value::Float(v), CastMode::Lossy) => (!v.is_nan()).then_some(*v as i64),
+        (Value::Text(text), _) => text.trim().parse::<i64>().ok().or_else(|| to_i64(&Value::Float(text.trim().parse::<f64>().ok()?), mode))
+    }
+}
+
+fn to_f64(value: &Value, mode: CastMode) -> Option<f64> {
+    match (value, mode) {
+        (Value::Bool(v), _) => Some(*v as i64 as f64),
+        (Value::Int(v), CastMode::Checked) => (v.unsigned_abs() <= MAX_EXACT_F64).then_some(*v as f64),
+        (Value::Int(v), CastMode::Lossy) => Some(*v as f64),
+        (Value::Float(v), _) => Some(*v),
+        (Value::Text(text), _) => text.trim().parse::<f64>().ok()
+    }
+}
+
+fn to_bool(value: &Value, mode: CastMode) -> Option<bool> {
+    match value {
+        Value::Bool(v) => return Some(*v),
+        Value::Text(text) if text.trim() == "true" => return Some(true),
+        Value::Text(text) if text.trim() == "false" => return Some(false),
+        _ => {}
+    }
+
+    let v: f64 = to_f64(value, CastMode::Lossy)?;
+    match mode {
+        CastMode::Checked => (v == 0.0 || v == 1.0).then_some(v == 1.0),
+        CastMode::Lossy => (!v.is_nan()).then_some(v != 0.0)
+    }
+}
+
+fn to_text(value: &Value) -> String {
+    match value {
+        Value::Bool(v) => v.to_string(),
+        Value::Int(v) => v.to_string(),
+        Value::Float(v) => v.to_string(),
+        Value::Text(text) => text.clone()
+    }
+}
+
+fn cast_rows<T: ColumnType>(column: &DataVector, mode: CastMode, convert: impl Fn(&Value) -> Option<T>) -> Result<DataVector, DataLoaderError> {
+    let label: &str = column.label.as_deref().unwrap_or_default();
+
+    let values: Vec<Option<T>> = (0..column.len)
+        .map(|row| {
+            if column.is_null(row) {
+                return Ok(None);
+            }
+
+            let value: Value = source_value(&column.data, row).ok_or(DataLoaderError::CastError(format!("Column '{}' is {} and cannot be cast to {}", label, column.dtype(), T::DTYPE)))?;
+
+            match (convert(&value), mode) {
+                (Some(converted), _) => Ok(Some(converted)),
+                (None, CastMode::Lossy) => Ok(None),
+                (None, CastMode::Checked) => Err(DataLoaderError::CastError(format!("'{}' in row {} of column '{}' cannot be cast to {} exactly", to_text(&value), row, label, T::DTYPE)))
+            }
+        })
+        .collect::<Result<Vec<Option<T>>, DataLoaderError>>()?;
+
+    Ok(DataVector::from_nullable(column.label.clone(), values))
+}
+
+impl DataVector {
+    pub fn cast<T: ColumnType>(&self, mode: CastMode) -> Result<DataVector, DataLoaderError> {
+        self.cast_to(T::DTYPE, mode)
+    }
+
+    // Converts between bool, numeric and text dtypes; text is parsed when cast to bool or a
+    // number. Datetime and duration columns can only be cast to their own dtype.
+    pub fn cast_to(&self, dtype: DType, mode: CastMode) -> Result<DataVector, DataLoaderError> {
+        if dtype == self.dtype() {
+            return Ok(self.clone());
+        }
+
+        match dtype {
+            DType::Bool => cast_rows(self, mode, |value| to_bool(value, mode)),
+            DType::I32 => cast_rows(self, mode, |value| {
+                let v: i64 = to_i64(value, mode)?;
+                match mode {
+                    CastMode::Checked => i32::try_from(v).ok(),
+                    CastMode::Lossy => Some(v.clamp(i32::MIN as i64, i32::MAX as i64) as i32)
+                }
+            }),
+            DType::I64 => cast_rows(self, mode, |value| to_i64(value, mode)),
+            DType::F32 => cast_rows(self, mode, |value| {
+                let v: f64 = to_f64(value, mode)?;
+                match mode {
+                    CastMode::Checked => (v as f32 as f64 == v || v.is_nan()).then_some(v as f32),
+                    CastMode::Lossy => Some(v as f32)
+                }
+            }),
+            DType::F64 => cast_rows(self, mode, |value| to_f64(value, mode)),
+            DType::Utf8 => cast_rows(self, mode, |value| Some(to_text(value))),
+            DType::Categorical => self.cast_to(DType::Utf8, mode)?.to_categorical(),
+            DType::Datetime | DType::Duration => Err(DataLoaderError::CastError(format!("Column '{}' is {} and cannot be cast to {}", self.label.as_deref().unwrap_or_default(), self.dtype(), dtype)))
+        }
+    }
+}
+
+impl DataMatrix {
+    // Casts the named columns, or every column when none are named.
+    pub fn cast<T: ColumnType>(&self, columns: Vec<&str>, mode: CastMode) -> Result<DataMatrix, DataLoaderError> {
+        for &name in columns.iter() {
+            if !self.columns.contains_key(name) {
+                return Err(DataLoaderError::GenericError(format!("Column '{}' does not exist", name)));
+            }
+        }
+
+        let mut cast: DataMatrix = self.clone();
+
+        for (name, column) in cast.columns.iter_mut() {
+            if columns.is_empty() || columns.contains(&name.as_str()) {
+                *column = column.cast::<T>(mode)?;
+            }
+        }
+
+        Ok(cast)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bools_cast_to_text_as_true_and_false() {
+        let column: DataVector = DataVector::from_nullable(Some("flag".to_string()), vec![Some(true), None, Some(false)]);
+
+        let text: Vec<Option<String>> = column.cast_to(DType::Utf8, CastMode::Checked).unwrap().to_options::<String>().unwrap();
+        assert_eq!(text, vec![Some("true".to_string()), None, Some("false".to_string())]);
+
+        let categories: Vec<Option<String>> = column.cast_to(DType::Categorical, CastMode::Checked).unwrap().to_options::<String>().unwrap();
+        assert_eq!(categories, text);
+
+        let numbers: Vec<Option<f64>> = column.cast::<f64>(CastMode::Checked).unwrap().to_options::<f64>().unwrap();
+        assert_eq!(numbers, vec![Some(1.0), None, Some(0.0)]);
+    }
+}
@@ -0,0 +1,324 @@
+use std::{any::Any, cmp::Ordering, collections::{HashMap, HashSet}};
+
+use chrono::DateTime;
+use veracity_types::dtype::DType;
+
+use crate::enums::error_types::DataLoaderError;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColumnData {
+    Bool(Vec<bool>),
+    I32(Vec<i32>),
+    I64(Vec<i64>),
+    F32(Vec<f32>),
+    F64(Vec<f64>),
+    Utf8(Vec<String>),
+    Categorical(Categorical),
+    // Microseconds since the Unix epoch, without a time zone.
+    Datetime(Vec<i64>),
+    // Microseconds.
+    Duration(Vec<i64>)
+}
+
+// Dictionary encoded strings: each row stores an index into categories. Null rows keep
+// code 0 and are tracked by the owning DataVector's validity.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Categorical {
+    pub categories: Vec<String>,
+    pub codes: Vec<u32>
+}
+
+impl Categorical {
+    // Encodes against the sorted distinct values.
+    pub fn encode(values: &[Option<String>]) -> Self {
+        let mut categories: Vec<String> = values.iter().flatten().cloned().collect::<HashSet<String>>().into_iter().collect();
+        categories.sort();
+
+        Self::encode_with(values, categories).expect("every value is one of its own categories")
+    }
+
+    // Encodes against a fixed dictionary, failing on any value outside it.
+    pub fn encode_with(values: &[Option<String>], categories: Vec<String>) -> Result<Self, DataLoaderError> {
+        let positions: HashMap<&String, u32> = categories.iter().enumerate().map(|(code, category)| (category, code as u32)).collect();
+
+        let codes: Vec<u32> = values
+            .iter()
+            .map(|value| match value {
+                Some(value) => positions.get(value).copied().ok_or(DataLoaderError::GenericError(format!("'{}' is not one of the categories", value))),
+                None => Ok(0)
+            })
+            .collect::<Result<Vec<u32>, DataLoaderError>>()?;
+
+        Ok(Categorical { categories, codes })
+    }
+
+    pub fn value(&self, index: usize) -> Option<&String> {
+        self.codes.get(index).and_then(|&code| self.categories.get(code as usize))
+    }
+
+    fn take(&self, rows: impl Iterator<Item = Option<usize>>) -> Self {
+        Categorical {
+            categories: self.categories.clone(),
+            codes: rows.map(|row| row.map(|row| self.codes[row]).unwrap_or_default()).collect()
+        }
+    }
+
+    // Appends other's rows, adding any of its categories this dictionary lacks.
+    fn extend(&mut self, other: &Categorical) {
+        let mut positions: HashMap<String, u32> = self.categories.iter().enumerate().map(|(code, category)| (category.clone(), code as u32)).collect();
+
+        let remapped: Vec<u32> = other.categories
+            .iter()
+            .map(|category| {
+                *positions.entry(category.clone()).or_insert_with(|| {
+                    self.categories.push(category.clone());
+                    (self.categories.len() - 1) as u32
+                })
+            })
+            .collect();
+
+        self.codes.extend(other.codes.iter().map(|&code| remapped.get(code as usize).copied().unwrap_or_default()));
+    }
+}
+
+// Runs the same expression against whichever Vec a ColumnData holds. Categorical
+// columns are not a plain Vec, so they get their own expression.
+macro_rules! with_column {
+    ($data:expr, $vec:ident => $body:expr, $cat:ident => $cat_body:expr) => {
+        match $data {
+            ColumnData::Bool($vec) => $body,
+            ColumnData::I32($vec) => $body,
+            ColumnData::I64($vec) => $body,
+            ColumnData::F32($vec) => $body,
+            ColumnData::F64($vec) => $body,
+            ColumnData::Utf8($vec) => $body,
+            ColumnData::Datetime($vec) => $body,
+            ColumnData::Duration($vec) => $body,
+            ColumnData::Categorical($cat) => $cat_body
+        }
+    };
+}
+
+// Like with_column, but rewraps the resulting Vec in the same variant.
+macro_rules! map_column {
+    ($data:expr, $vec:ident => $body:expr, $cat:ident => $cat_body:expr) => {
+        match $data {
+            ColumnData::Bool($vec) => ColumnData::Bool($body),
+            ColumnData::I32($vec) => ColumnData::I32($body),
+            ColumnData::I64($vec) => ColumnData::I64($body),
+            ColumnData::F32($vec) => ColumnData::F32($body),
+            ColumnData::F64($vec) => ColumnData::F64($body),
+            ColumnData::Utf8($vec) => ColumnData::Utf8($body),
+            ColumnData::Datetime($vec) => ColumnData::Datetime($body),
+            ColumnData::Duration($vec) => ColumnData::Duration($body),
+            ColumnData::Categorical($cat) => ColumnData::Categorical($cat_body)
+        }
+    };
+}
+
+pub trait ColumnType: Clone + Default + Send + Sync + 'static {
+    const DTYPE: DType;
+
+    fn into_column(vec: Vec<Self>) -> ColumnData;
+}
+
+impl ColumnType for bool {
+    const DTYPE: DType = DType::Bool;
+
+    fn into_column(vec: Vec<Self>) -> ColumnData {
+        ColumnData::Bool(vec)
+    }
+}
+
+impl ColumnType for i32 {
+    const DTYPE: DType = DType::I32;
+
+    fn into_column(vec: Vec<Self>) -> ColumnData {
+        ColumnData::I32(vec)
+    }
+}
+
+impl ColumnType for i64 {
+    const DTYPE: DType = DType::I64;
+
+    fn into_column(vec: Vec<Self>) -> ColumnData {
+        ColumnData::I64(vec)
+    }
+}
+
+impl ColumnType for f32 {
+    const DTYPE: DType = DType::F32;
+
+    fn into_column(vec: Vec<Self>) -> ColumnData {
+        ColumnData::F32(vec)
+    }
+}
+
+impl ColumnType for f64 {
+    const DTYPE: DType = DType::F64;
+
+    fn into_column(vec: Vec<Self>) -> ColumnData {
+        ColumnData::F64(vec)
+    }
+}
+
+impl ColumnType for String {
+    const DTYPE: DType = DType::Utf8;
+
+    fn into_column(vec: Vec<Self>) -> ColumnData {
+        ColumnData::Utf8(vec)
+    }
+}
+
+pub(crate) fn format_datetime(micros: i64) -> String {
+    DateTime::from_timestamp_micros(micros).map(|datetime| datetime.naive_utc().to_string()).unwrap_or_else(|| micros.to_string())
+}
+
+// Formats as "[-]{days} days HH:MM:SS[.ffffff]".
+pub(crate) fn format_duration(micros: i64) -> String {
+    let sign: &str = if micros < 0 { "-" } else { "" };
+    let micros: u64 = micros.unsigned_abs();
+    let seconds: u64 = micros / 1_000_000;
+    let fraction: u64 = micros % 1_000_000;
+
+    let mut formatted: String = format!("{}{} days {:02}:{:02}:{:02}", sign, seconds / 86_400, seconds % 86_400 / 3_600, seconds % 3_600 / 60, seconds % 60);
+    if fraction > 0 {
+        formatted.push_str(&format!(".{:06}", fraction));
+    }
+    formatted
+}
+
+impl ColumnData {
+    pub fn empty(dtype: DType) -> Self {
+        match dtype {
+            DType::Bool => ColumnData::Bool(Vec::new()),
+            DType::I32 => ColumnData::I32(Vec::new()),
+            DType::I64 => ColumnData::I64(Vec::new()),
+            DType::F32 => ColumnData::F32(Vec::new()),
+            DType::F64 => ColumnData::F64(Vec::new()),
+            DType::Utf8 => ColumnData::Utf8(Vec::new()),
+            DType::Categorical => ColumnData::Categorical(Categorical { categories: Vec::new(), codes: Vec::new() }),
+            DType::Datetime => ColumnData::Datetime(Vec::new()),
+            DType::Duration => ColumnData::Duration(Vec::new())
+        }
+    }
+
+    pub fn dtype(&self) -> DType {
+        match self {
+            ColumnData::Bool(_) => DType::Bool,
+            ColumnData::I32(_) => DType::I32,
+            ColumnData::I64(_) => DType::I64,
+            ColumnData::F32(_) => DType::F32,
+            ColumnData::F64(_) => DType::F64,
+            ColumnData::Utf8(_) => DType::Utf8,
+            ColumnData::Categorical(_) => DType::Categorical,
+            ColumnData::Datetime(_) => DType::Datetime,
+            ColumnData::Duration(_) => DType::Duration
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        with_column!(self, vec => vec.len(), cat => cat.codes.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn as_vec<T: 'static>(&self) -> Option<&Vec<T>> {
+        with_column!(self, vec => (vec as &dyn Any).downcast_ref::<Vec<T>>(), _cat => None)
+    }
+
+    pub fn as_vec_mut<T: 'static>(&mut self) -> Option<&mut Vec<T>> {
+        with_column!(self, vec => (vec as &mut dyn Any).downcast_mut::<Vec<T>>(), _cat => None)
+    }
+
+    #[allow(clippy::clone_on_copy)]
+    pub(crate) fn take(&self, rows: &[usize]) -> ColumnData {
+        map_column!(self, vec => rows.iter().map(|&row| vec[row].clone()).collect(), cat => cat.take(rows.iter().map(|&row| Some(row))))
+    }
+
+    // Rows given as None are filled with the type's default value.
+    #[allow(clippy::clone_on_copy)]
+    pub(crate) fn take_optional(&self, rows: &[Option<usize>]) -> ColumnData {
+        map_column!(self, vec => rows.iter().map(|row| row.map(|row| vec[row].clone()).unwrap_or_default()).collect(), cat => cat.take(rows.iter().copied()))
+    }
+
+    // Appends other's values when it holds the same type; returns false otherwise.
+    pub(crate) fn extend(&mut self, other: &ColumnData) -> bool {
+        with_column!(self, vec => match other.as_vec() {
+            Some(values) => {
+                vec.extend_from_slice(values);
+                true
+            },
+            None => false
+        }, cat => match other {
+            ColumnData::Categorical(other) => {
+                cat.extend(other);
+                true
+            },
+            _ => false
+        })
+    }
+
+    // Floats are ordered with total_cmp so NaN sorts consistently.
+    pub(crate) fn compare_rows(&self, a: usize, b: usize) -> Ordering {
+        match self {
+            ColumnData::Bool(vec) => vec[a].cmp(&vec[b]),
+            ColumnData::I32(vec) => vec[a].cmp(&vec[b]),
+            ColumnData::I64(vec) => vec[a].cmp(&vec[b]),
+            ColumnData::F32(vec) => vec[a].total_cmp(&vec[b]),
+            ColumnData::F64(vec) => vec[a].total_cmp(&vec[b]),
+            ColumnData::Utf8(vec) => vec[a].cmp(&vec[b]),
+            ColumnData::Categorical(cat) => cat.value(a).cmp(&cat.value(b)),
+            ColumnData::Datetime(vec) | ColumnData::Duration(vec) => vec[a].cmp(&vec[b])
+        }
+    }
+
+    // Numeric view of a single value; None for string, categorical and temporal columns.
+    pub(crate) fn value_as_f64(&self, index: usize) -> Option<f64> {
+        match self {
+            ColumnData::Bool(vec) => Some(if vec[index] { 1.0 } else { 0.0 }),
+            ColumnData::I32(vec) => Some(vec[index] as f64),
+            ColumnData::I64(vec) => Some(vec[index] as f64),
+            ColumnData::F32(vec) => Some(vec[index] as f64),
+            ColumnData::F64(vec) => Some(vec[index]),
+            ColumnData::Utf8(_) | ColumnData::Categorical(_) | ColumnData::Datetime(_) | ColumnData::Duration(_) => None
+        }
+    }
+
+    // Exact integer view of a single value; None for float and string columns.
+    pub(crate) fn value_as_i64(&self, index: usize) -> Option<i64> {
+        match self {
+            ColumnData::Bool(vec) => Some(vec[index] as i64),
+            ColumnData::I32(vec) => Some(vec[index] as i64),
+            ColumnData::I64(vec) => Some(vec[index]),
+            _ => None
+        }
+    }
+
+    pub(crate) fn format_value(&self, index: usize) -> Option<String> {
+        match self {
+            ColumnData::F32(vec) => vec.get(index).map(|v| format!("{:.3}", v)),
+            ColumnData::F64(vec) => vec.get(index).map(|v| format!("{:.3}", v)),
+            data => data.display_value(index)
+        }
+    }
+
+    // Plain text form of a value, used where values become labels.
+    pub(crate) fn display_value(&self, index: usize) -> Option<String> {
+        match self {
+            ColumnData::Datetime(vec) => return vec.get(index).map(|&v| format_datetime(v)),
+            ColumnData::Duration(vec) => return vec.get(index).map(|&v| format_duration(v)),
+            _ => {}
+        }
+
+        with_column!(self, vec => vec.get(index).map(|v| v.to_string()), cat => cat.value(index).cloned())
+    }
+
+    pub(crate) fn format_debug(&self, index: usize) -> Option<String> {
+        with_column!(self, vec => vec.get(index).map(|v| format!("{:?}", v)), cat => cat.value(index).map(|v| format!("{:?}", v)))
+    }
+}
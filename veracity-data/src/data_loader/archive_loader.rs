@@ -0,0 +1,182 @@
+use std::{fs::File, io::Read, path::Path};
+
+use async_trait::async_trait;
+use flate2::read::GzDecoder;
+use glob::Pattern;
+use zip::ZipArchive;
+
+use crate::{data_matrix::DataMatrix, enums::error_types::DataLoaderError};
+
+use super::{csv_loader::{CSVLoader, CSVLoaderSettings}, data_loader_settings::DataLoaderSettings, data_types::DataTypes, json_loader::{JSONLoader, JSONLoaderSettings}, xml_loader::{XMLLoader, XMLLoaderSettings}, DataLoader};
+
+pub struct ArchiveLoader {
+    pub settings: ArchiveLoaderSettings
+}
+
+#[derive(Default)]
+pub struct ArchiveLoaderSettings {
+    pub inner_file: Option<String>,
+    pub csv_settings: CSVLoaderSettings,
+    pub json_settings: JSONLoaderSettings,
+    pub xml_settings: XMLLoaderSettings
+}
+
+impl DataLoaderSettings for ArchiveLoaderSettings {}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Compression {
+    Zip,
+    Gzip,
+    Zstd
+}
+
+impl ArchiveLoader {
+    pub fn new(settings: ArchiveLoaderSettings) -> Self {
+        ArchiveLoader {
+            settings
+        }
+    }
+
+    fn compression(path: &str) -> Result<Compression, DataLoaderError> {
+        let extension: String = Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+
+        match extension.as_str() {
+            "zip" => Ok(Compression::Zip),
+            "gz" => Ok(Compression::Gzip),
+            "zst" | "zstd" => Ok(Compression::Zstd),
+            _ => Err(DataLoaderError::GenericError(format!("'{}' is not a .zip, .gz or .zst archive", path)))
+        }
+    }
+
+    fn select_zip_entry(&self, names: &[String]) -> Result<String, DataLoaderError> {
+        let files: Vec<&String> = names.iter().filter(|name| !name.ends_with('/')).collect();
+
+        let candidates: Vec<&String> = match &self.settings.inner_file {
+            Some(inner_file) if files.contains(&inner_file) => {
+                return Ok(inner_file.clone());
+            },
+            Some(inner_file) => {
+                let pattern: Pattern = Pattern::new(inner_file).map_err(|e| DataLoaderError::GenericError(e.to_string()))?;
+                files.into_iter().filter(|name| pattern.matches(name)).collect()
+            },
+            None => files
+                .into_iter()
+                .filter(|name| matches!(DataTypes::from_path(name), Some(DataTypes::CSV) | Some(DataTypes::JSON) | Some(DataTypes::XML)))
+                .collect()
+        };
+
+        match candidates.as_slice() {
+            [] => Err(DataLoaderError::GenericError("No file in the archive matched inner_file".to_string())),
+            [name] => Ok((*name).clone()),
+            _ => Err(DataLoaderError::GenericError(format!("Multiple files in the archive matched, set inner_file to one of: {:?}", candidates)))
+        }
+    }
+
+    fn load_inner<R: Read>(&self, name: &str, reader: R) -> Result<DataMatrix, DataLoaderError> {
+        match DataTypes::from_path(name) {
+            Some(DataTypes::CSV) => CSVLoader::new(self.settings.csv_settings.clone()).load_from_reader(reader),
+            Some(DataTypes::JSON) => JSONLoader::new(self.settings.json_settings.clone()).load_from_reader(reader),
+            Some(DataTypes::XML) => XMLLoader::new(self.settings.xml_settings.clone()).load_from_reader(reader),
+            _ => Err(DataLoaderError::GenericError(format!("Unable to determine a CSV, JSON or XML loader for '{}'", name)))
+        }
+    }
+}
+
+#[async_trait]
+impl DataLoader for ArchiveLoader {
+    async fn load_from<'a>(&'a self, path: &'a str) -> Result<DataMatrix, DataLoaderError> {
+        let file: File = File::open(path).map_err(|e| DataLoaderError::FileRead(e.to_string()))?;
+        let stem: &str = Path::new(path).file_stem().and_then(|stem| stem.to_str()).unwrap_or("");
+
+        match Self::compression(path)? {
+            Compression::Zip => {
+                let mut archive: ZipArchive<File> = ZipArchive::new(file).map_err(|e| DataLoaderError::FileRead(e.to_string()))?;
+                let names: Vec<String> = archive.file_names().map(|name| name.to_string()).collect();
+                let name: String = self.select_zip_entry(&names)?;
+                let entry = archive.by_name(&name).map_err(|e| DataLoaderError::FileRead(e.to_string()))?;
+                self.load_inner(&name, entry)
+            },
+            Compression::Gzip => {
+                let name: &str = self.settings.inner_file.as_deref().unwrap_or(stem);
+                self.load_inner(name, GzDecoder::new(file))
+            },
+            Compression::Zstd => {
+                let name: &str = self.settings.inner_file.as_deref().unwrap_or(stem);
+                let decoder = zstd::Decoder::new(file).map_err(|e| DataLoaderError::FileRead(e.to_string()))?;
+                self.load_inner(name, decoder)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{write::GzEncoder, Compression as GzCompression};
+    use zip::{write::SimpleFileOptions, ZipWriter};
+
+    use super::*;
+
+    const CSV: &str = "id,name\n1,a\n2,b\n";
+    const JSON: &str = "[{\"id\": 3, \"name\": \"c\"}]";
+
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir().join(format!("veracity_archive_{}_{}", std::process::id(), name)).to_str().unwrap().to_string()
+    }
+
+    fn write_zip(path: &str) {
+        let mut writer: ZipWriter<File> = ZipWriter::new(File::create(path).unwrap());
+        writer.add_directory("data/", SimpleFileOptions::default()).unwrap();
+        for (name, contents) in [("data/a.csv", CSV), ("data/b.json", JSON), ("README.md", "not data")] {
+            writer.start_file(name, SimpleFileOptions::default()).unwrap();
+            writer.write_all(contents.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    async fn load(path: &str, inner_file: Option<&str>) -> Result<DataMatrix, DataLoaderError> {
+        let settings: ArchiveLoaderSettings = ArchiveLoaderSettings { inner_file: inner_file.map(str::to_string), ..Default::default() };
+        ArchiveLoader::new(settings).load_from(path).await
+    }
+
+    fn ids(matrix: &DataMatrix) -> Vec<Option<i64>> {
+        matrix.get_column("id").unwrap().to_options::<i64>().unwrap()
+    }
+
+    #[tokio::test]
+    async fn zip_entries_are_picked_by_name_or_glob() {
+        let path: String = temp_path("entries.zip");
+        write_zip(&path);
+
+        assert_eq!(ids(&load(&path, Some("data/*.csv")).await.unwrap()), vec![Some(1), Some(2)]);
+        assert_eq!(ids(&load(&path, Some("data/b.json")).await.unwrap()), vec![Some(3)]);
+        assert!(load(&path, None).await.is_err());
+        assert!(load(&path, Some("*.parquet")).await.is_err());
+        assert!(load(&path, Some("README.md")).await.is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn gzip_and_zstd_files_use_the_inner_extension() {
+        let gz_path: String = temp_path("people.csv.gz");
+        let mut encoder: GzEncoder<File> = GzEncoder::new(File::create(&gz_path).unwrap(), GzCompression::default());
+        encoder.write_all(CSV.as_bytes()).unwrap();
+        encoder.finish().unwrap();
+
+        let zst_path: String = temp_path("people.zst");
+        std::fs::write(&zst_path, zstd::encode_all(JSON.as_bytes(), 0).unwrap()).unwrap();
+
+        assert_eq!(ids(&load(&gz_path, None).await.unwrap()), vec![Some(1), Some(2)]);
+        assert!(load(&zst_path, None).await.is_err());
+        assert_eq!(ids(&load(&zst_path, Some("people.json")).await.unwrap()), vec![Some(3)]);
+
+        std::fs::remove_file(&gz_path).unwrap();
+        std::fs::remove_file(&zst_path).unwrap();
+    }
+}
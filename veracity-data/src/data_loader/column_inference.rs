@@ -0,0 +1,128 @@
+use std::collections::HashSet;
+
+use veracity_types::dtype::DType;
+
+use crate::{data_vector::DataVector, enums::error_types::DataLoaderError, temporal::parse_datetime};
+
+fn try_parse<T: std::str::FromStr>(values: &[Option<impl AsRef<str>>]) -> Option<Vec<Option<T>>> {
+    let mut out = Vec::with_capacity(values.len());
+    for v in values {
+        match v {
+            Some(v) => match v.as_ref().parse::<T>() {
+                Ok(parsed) => out.push(Some(parsed)),
+                Err(_) => return None,
+            },
+            None => out.push(None),
+        }
+    }
+    Some(out)
+}
+
+fn parse_bool<S: AsRef<str>>(values: &[Option<S>]) -> Option<Vec<Option<bool>>> {
+    values
+        .iter()
+        .map(|v| match v.as_ref().map(AsRef::as_ref) {
+            Some("true") => Some(Some(true)),
+            Some("false") => Some(Some(false)),
+            Some(_) => None,
+            None => Some(None)
+        })
+        .collect()
+}
+
+fn parses_as(value: &str, dtype: DType, date_formats: &[String]) -> bool {
+    match dtype {
+        DType::Bool => value == "true" || value == "false",
+        DType::I32 => value.parse::<i32>().is_ok(),
+        DType::I64 | DType::Duration => value.parse::<i64>().is_ok(),
+        DType::F32 => value.parse::<f32>().is_ok(),
+        DType::F64 => value.parse::<f64>().is_ok(),
+        DType::Datetime => parse_datetime(value, date_formats).is_some(),
+        DType::Utf8 | DType::Categorical => true
+    }
+}
+
+fn into_strings<S: Into<String>>(values: Vec<Option<S>>) -> Vec<Option<String>> {
+    values.into_iter().map(|v| v.map(Into::into)).collect()
+}
+
+// Infers a column's dtype from its raw text, trying bool, i64, f64 and then
+// categorical before falling back to String. Integers too large for i64 fail to parse
+// and fall through to f64. Text columns become categorical when they have at most
+// categorical_threshold distinct values. Values may be borrowed, so only text columns
+// allocate. Missing values (None) become nulls in the resulting column.
+pub(crate) fn infer_column<S: AsRef<str> + Into<String>>(header: String, values: Vec<Option<S>>, categorical_threshold: Option<usize>) -> DataVector {
+    let has_values: bool = values.iter().any(|v| v.is_some());
+
+    if has_values && let Some(parsed) = parse_bool(&values) {
+        return DataVector::from_nullable(Some(header), parsed);
+    }
+
+    if has_values && let Some(parsed) = try_parse::<i64>(&values) {
+        return DataVector::from_nullable(Some(header), parsed);
+    }
+
+    if has_values && let Some(parsed) = try_parse::<f64>(&values) {
+        return DataVector::from_nullable(Some(header), parsed);
+    }
+
+    if has_values && categorical_threshold.is_some_and(|threshold| values.iter().flatten().map(AsRef::as_ref).collect::<HashSet<&str>>().len() <= threshold) {
+        return DataVector::from_categorical(Some(header), into_strings(values));
+    }
+
+    DataVector::from_nullable(Some(header), into_strings(values))
+}
+
+// Parses a column into a dtype chosen earlier by infer_column, so that later chunks
+// of a file keep the schema of the first one instead of being inferred again. Datetime
+// values are parsed with date_formats; durations are read as whole microseconds.
+pub(crate) fn parse_column<S: AsRef<str> + Into<String>>(header: String, values: Vec<Option<S>>, dtype: DType, date_formats: &[String]) -> Result<DataVector, DataLoaderError> {
+    let mismatch = |header: &str| {
+        let (row, value) = values
+            .iter()
+            .enumerate()
+            .find_map(|(row, v)| v.as_ref().map(AsRef::as_ref).filter(|v| !parses_as(v, dtype, date_formats)).map(|v| (row, v)))
+            .unwrap_or_default();
+        DataLoaderError::ParseError(format!("Column '{}' has '{}' in row {}, which does not match its {} dtype", header, value, row, dtype))
+    };
+
+    match dtype {
+        DType::Bool => {
+            let parsed: Vec<Option<bool>> = parse_bool(&values).ok_or_else(|| mismatch(&header))?;
+            Ok(DataVector::from_nullable(Some(header), parsed))
+        },
+        DType::I32 => {
+            let parsed: Vec<Option<i32>> = try_parse::<i32>(&values).ok_or_else(|| mismatch(&header))?;
+            Ok(DataVector::from_nullable(Some(header), parsed))
+        },
+        DType::I64 => {
+            let parsed: Vec<Option<i64>> = try_parse::<i64>(&values).ok_or_else(|| mismatch(&header))?;
+            Ok(DataVector::from_nullable(Some(header), parsed))
+        },
+        DType::F32 => {
+            let parsed: Vec<Option<f32>> = try_parse::<f32>(&values).ok_or_else(|| mismatch(&header))?;
+            Ok(DataVector::from_nullable(Some(header), parsed))
+        },
+        DType::F64 => {
+            let parsed: Vec<Option<f64>> = try_parse::<f64>(&values).ok_or_else(|| mismatch(&header))?;
+            Ok(DataVector::from_nullable(Some(header), parsed))
+        },
+        DType::Utf8 => Ok(DataVector::from_nullable(Some(header), into_strings(values))),
+        DType::Categorical => Ok(DataVector::from_categorical(Some(header), into_strings(values))),
+        DType::Datetime => {
+            let parsed: Vec<Option<i64>> = values
+                .iter()
+                .map(|v| match v {
+                    Some(v) => parse_datetime(v.as_ref(), date_formats).map(Some),
+                    None => Some(None)
+                })
+                .collect::<Option<Vec<Option<i64>>>>()
+                .ok_or_else(|| mismatch(&header))?;
+            Ok(DataVector::from_micros(Some(header), parsed, DType::Datetime))
+        },
+        DType::Duration => {
+            let parsed: Vec<Option<i64>> = try_parse::<i64>(&values).ok_or_else(|| mismatch(&header))?;
+            Ok(DataVector::from_micros(Some(header), parsed, DType::Duration))
+        }
+    }
+}
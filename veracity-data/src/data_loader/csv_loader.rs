@@ -1,17 +1,23 @@
-use std::{collections::BTreeMap, io::{BufRead, BufReader}, usize};
+use std::{collections::HashMap, io::{BufReader, Read}};
 
 use async_trait::async_trait;
+use indexmap::IndexMap;
+use veracity_types::dtype::DType;
 
-use crate::{data_matrix::DataMatrix, data_vector::DataVector, enums::error_types::DataLoaderError};
+use crate::{data_matrix::DataMatrix, data_vector::DataVector, enums::error_types::DataLoaderError, schema::Schema, temporal::default_date_formats};
 
-use super::{data_loader_settings::DataLoaderSettings, DataLoader};
+use super::{column_inference::{infer_column, parse_column}, csv_records::CSVRecords, data_loader_settings::DataLoaderSettings, DataLoader};
 
 pub struct CSVLoader {
     pub settings: CSVLoaderSettings
 }
 
+#[derive(Clone)]
 pub struct CSVLoaderSettings {
     pub separator: char,
+    pub quote_char: Option<char>,
+    pub escape_char: Option<char>,
+    pub comment_prefix: Option<String>,
     pub header_names: Vec<String>,
     pub header_indices: Vec<usize>,
     pub index_col: Option<usize>,
@@ -19,13 +25,22 @@ pub struct CSVLoaderSettings {
     pub skip_rows: usize,
     pub skip_footer: usize,
     pub n_rows: usize,
-    pub skip_blank_lines: bool
+    pub skip_blank_lines: bool,
+    pub na_values: Vec<String>,
+    pub categorical_threshold: Option<usize>,
+    pub parse_dates: Vec<String>,
+    pub date_formats: Vec<String>,
+    pub dtypes: HashMap<String, DType>,
+    pub schema: Option<Schema>
 }
 
 impl Default for CSVLoaderSettings {
     fn default() -> Self {
         Self {
             separator: ',',
+            quote_char: Some('"'),
+            escape_char: None,
+            comment_prefix: None,
             header_names: Vec::new(),
             header_indices: Vec::new(),
             index_col: None,
@@ -34,12 +49,101 @@ impl Default for CSVLoaderSettings {
             skip_footer: 0,
             n_rows: usize::MAX,
             skip_blank_lines: true,
+            na_values: ["", "NA", "N/A", "n/a", "NaN", "nan", "-NaN", "null", "NULL", "None", "#N/A"]
+                .iter()
+                .map(|na| na.to_string())
+                .collect(),
+            categorical_threshold: None,
+            parse_dates: Vec::new(),
+            date_formats: default_date_formats(),
+            dtypes: HashMap::new(),
+            schema: None
         }
     }
 }
 
 impl DataLoaderSettings for CSVLoaderSettings {}
 
+pub struct CSVChunks<R: Read> {
+    records: CSVRecords<BufReader<R>>,
+    headers: Vec<String>,
+    pending: Option<Vec<String>>,
+    dtypes: Vec<Option<DType>>,
+    chunk_size: usize,
+    remaining_rows: usize,
+    record_number: usize,
+    skip_blank_lines: bool,
+    na_values: Vec<String>,
+    column_reader: ColumnReader
+}
+
+// Turns raw column values into DataVectors, shared by whole-file and chunked reads.
+#[derive(Clone)]
+pub(super) struct ColumnReader {
+    schema: Option<Schema>,
+    dtypes: HashMap<String, DType>,
+    parse_dates: Vec<String>,
+    date_formats: Vec<String>,
+    categorical_threshold: Option<usize>
+}
+
+// Fields matching one of na_values are read as nulls.
+fn parse_fields(record: Vec<String>, na_values: &[String]) -> Vec<Option<String>> {
+    record
+        .into_iter()
+        .map(|field| if na_values.contains(&field) { None } else { Some(field) })
+        .collect()
+}
+
+impl ColumnReader {
+    pub(super) fn new(settings: &CSVLoaderSettings) -> Self {
+        ColumnReader {
+            schema: settings.schema.clone(),
+            dtypes: settings.dtypes.clone(),
+            parse_dates: settings.parse_dates.clone(),
+            date_formats: settings.date_formats.clone(),
+            categorical_threshold: settings.categorical_threshold
+        }
+    }
+
+    pub(super) fn check_headers(&self, headers: &[String]) -> Result<(), DataLoaderError> {
+        if let Some(name) = self.parse_dates.iter().find(|name| !headers.contains(name)) {
+            return Err(DataLoaderError::GenericError(format!("parse_dates column '{}' does not exist", name)));
+        }
+        if let Some(name) = self.dtypes.keys().find(|name| !headers.contains(name)) {
+            return Err(DataLoaderError::GenericError(format!("dtypes column '{}' does not exist", name)));
+        }
+
+        let missing: Vec<String> = self.schema.iter().flat_map(|schema| schema.columns.iter()).map(|column| column.name.clone()).filter(|name| !headers.contains(name)).collect();
+        if !missing.is_empty() {
+            return Err(DataLoaderError::ColumnMismatch { missing, unexpected: Vec::new() });
+        }
+
+        Ok(())
+    }
+
+    // Schema dtypes take precedence over dtypes, then parse_dates; every other column is
+    // inferred.
+    fn forced_dtype(&self, header: &str) -> Option<DType> {
+        self.schema
+            .as_ref()
+            .and_then(|schema| schema.dtype(header))
+            .or(self.dtypes.get(header).copied())
+            .or(self.parse_dates.iter().any(|name| name == header).then_some(DType::Datetime))
+    }
+
+    pub(super) fn read<S: AsRef<str> + Into<String>>(&self, header: String, values: Vec<Option<S>>) -> Result<DataVector, DataLoaderError> {
+        match self.forced_dtype(&header) {
+            Some(dtype) => self.parse(header, values, dtype),
+            None => Ok(infer_column(header, values, self.categorical_threshold))
+        }
+    }
+
+    fn parse<S: AsRef<str> + Into<String>>(&self, header: String, values: Vec<Option<S>>, dtype: DType) -> Result<DataVector, DataLoaderError> {
+        parse_column(header, values, dtype, &self.date_formats)
+    }
+}
+
 impl CSVLoader {
     pub fn new(settings: CSVLoaderSettings) -> Self {
         CSVLoader { 
@@ -47,18 +151,18 @@ impl CSVLoader {
         }
     }
 
-    fn get_headers(&self, lines: &Vec<String>) -> Result<Vec<String>, DataLoaderError> {
-        if self.settings.header_names.len() >= 1 {
-            let first_line: &str = lines.get(0).ok_or(DataLoaderError::GenericError("CSV file contains no data".to_string()))?;
-            let column_count: usize = first_line.split(self.settings.separator).count();
+    pub(super) fn get_headers(&self, first_record: Option<&Vec<String>>) -> Result<Vec<String>, DataLoaderError> {
+        let first_record: &Vec<String> = first_record.ok_or(DataLoaderError::GenericError("CSV file contains no data".to_string()))?;
+
+        if !self.settings.header_names.is_empty() {
+            let column_count: usize = first_record.len();
             if column_count != self.settings.header_names.len() {
                 return Err(DataLoaderError::ColumnCountMismatch(format!("header_names property had {} values and the csv file had {} columns", self.settings.header_names.len(), column_count)));
             }
             Ok(self.settings.header_names.clone())
         }
-        else if self.settings.header_indices.len() >= 1 {
-            let first_line: &str = lines.get(0).ok_or(DataLoaderError::GenericError("CSV file contains no data".to_string()))?;
-            let headers: Vec<&str> = first_line.split(self.settings.separator).collect::<Vec<&str>>();
+        else if !self.settings.header_indices.is_empty() {
+            let headers: Vec<&str> = first_record.iter().map(|h| h.as_str()).collect::<Vec<&str>>();
             let header_count = headers.len();
             if header_count != self.settings.header_indices.len() {
                 return Err(DataLoaderError::ColumnCountMismatch(format!("header_indices property had {} values and the csv file had {} columns", self.settings.header_names.len(), header_count)));
@@ -72,115 +176,280 @@ impl CSVLoader {
             Ok(reordered_headers.iter().map(|&h| h.to_owned()).collect::<Vec<String>>())
         }
         else {
-            let first_line: &str = lines.get(0).ok_or(DataLoaderError::GenericError("CSV file contains no data".to_string()))?;
-            let headers: Vec<&str> = first_line.split(self.settings.separator).collect::<Vec<&str>>();
-            Ok(headers.iter().map(|&h| h.to_owned()).collect::<Vec<String>>())
-        }
-    }
-
-    fn try_parse<T: std::str::FromStr>(&self, values: &[String]) -> Option<Vec<T>> {
-        let mut out = Vec::with_capacity(values.len());
-        for v in values {
-            match v.parse::<T>() {
-                Ok(parsed) => out.push(parsed),
-                Err(_) => return None,
-            }
+            Ok(first_record.clone())
         }
-        Some(out)
     }
-}
 
-#[async_trait]
-impl DataLoader for CSVLoader {
-    async fn load_from<'a>(&'a self, path: &'a str) -> Result<DataMatrix, DataLoaderError> {
-        let file: std::fs::File = std::fs::File::open(path).map_err(|e| DataLoaderError::FileRead(e.to_string()))?;
-        let reader: BufReader<std::fs::File> = BufReader::new(file);
-        let mut lines: Vec<String> = reader.lines().collect::<Result<Vec<_>, _>>().map_err(|e| DataLoaderError::GenericError(e.to_string()))?;
+    pub fn load_from_reader<R: Read>(&self, reader: R) -> Result<DataMatrix, DataLoaderError> {
+        let column_reader: ColumnReader = ColumnReader::new(&self.settings);
+        let mut records: Vec<Vec<String>> = CSVRecords::new(BufReader::new(reader), &self.settings).collect::<Result<Vec<_>, _>>()?;
 
-        let headers: Vec<String> = self.get_headers(&lines)?;
+        let headers: Vec<String> = self.get_headers(records.iter().find(|record| !record.is_empty()))?;
+        column_reader.check_headers(&headers)?;
 
-        if self.settings.header_names.len() == 0 {
-            lines.remove(0);
+        if self.settings.header_names.is_empty() {
+            let header_position: usize = records.iter().position(|record| !record.is_empty()).unwrap_or(0);
+            records.drain(..=header_position);
         }
 
-        let mut raw_columns: BTreeMap<String, Vec<String>> = headers
+        let mut raw_columns: IndexMap<String, Vec<Option<String>>> = headers
             .iter()
             .map(|h| (h.clone(), Vec::new()))
             .collect();
 
         let mut index = Vec::new();
 
-        for (i, line) in lines.iter().enumerate() {
-            let line = if line.trim().is_empty() {
+        for (i, record) in records.into_iter().enumerate() {
+            let fields: Vec<Option<String>> = if record.is_empty() {
                 if self.settings.skip_blank_lines {
                     continue;
                 } else {
-                    format!("NA{}", self.settings.separator).repeat(headers.len())
+                    vec![None; headers.len()]
                 }
             } else {
-                line.to_string()
+                parse_fields(record, &self.settings.na_values)
             };
-            let fields: Vec<&str> = line.split(self.settings.separator).map(|s| s.trim()).collect();
             if fields.len() != headers.len() {
-                return Err(DataLoaderError::ColumnCountMismatch("There is a different number of columns in two or more rows.".to_string()));
+                return Err(DataLoaderError::ColumnCountMismatch(format!("Record {} has {} fields but the header has {} columns", i, fields.len(), headers.len())));
             }
 
             index.push(format!("{}", i));
 
-            for (header, field) in headers.iter().zip(fields.iter()) {
-                raw_columns.get_mut(header).unwrap().push(field.to_string());
+            for (header, field) in headers.iter().zip(fields) {
+                raw_columns.get_mut(header).unwrap().push(field);
             }
         }
 
-        let mut columns = BTreeMap::new();
+        // Rows dropped by skip_rows, skip_footer and n_rows are dropped from the index too.
+        let kept_rows: usize = index.len().saturating_sub(self.settings.skip_rows.max(self.settings.skip_footer)).min(self.settings.n_rows);
+        index.truncate(kept_rows);
+
+        let mut columns = IndexMap::new();
 
         for (header, values) in raw_columns {
-            let values = values[..values.len().saturating_sub(self.settings.skip_rows)].iter()
-                .take(values.len().saturating_sub(self.settings.skip_footer))
-                .take(self.settings.n_rows)
-                .map(|s| s.clone())
-                .collect::<Vec<String>>();
-
-            let row_count = values.len();
-
-            if values.iter().all(|v| v == "true" || v == "false") {
-                let parsed: Vec<bool> = values.iter().map(|v| v == "true").collect();
-                columns.insert(
-                    header.clone(),
-                    DataVector {
-                        label: Some(header),
-                        data: Box::new(parsed),
-                        len: row_count,
-                        dtype: Some("bool"),
-                    },
-                );
-                continue;
+            let values: Vec<Option<String>> = values.into_iter().take(kept_rows).collect();
+            columns.insert(header.clone(), column_reader.read(header, values)?);
+        }
+
+        Ok(DataMatrix { columns, index })
+    }
+
+    // Reads the file chunk_size rows at a time. A column's dtype is inferred from the
+    // first chunk holding any of its values and later chunks are parsed into it, except
+    // that an i64 column widens to f64 when a later chunk holds floats. Any other change
+    // of type is a ParseError, so pass dtypes or a schema when early rows are not
+    // representative.
+    pub fn load_chunks_from(&self, path: &str, chunk_size: usize) -> Result<CSVChunks<std::fs::File>, DataLoaderError> {
+        let file: std::fs::File = std::fs::File::open(path).map_err(|e| DataLoaderError::FileRead(e.to_string()))?;
+        self.chunks_from_reader(file, chunk_size)
+    }
+
+    pub fn chunks_from_reader<R: Read>(&self, reader: R, chunk_size: usize) -> Result<CSVChunks<R>, DataLoaderError> {
+        if chunk_size == 0 {
+            return Err(DataLoaderError::GenericError("chunk_size must be greater than zero".to_string()));
+        }
+        if self.settings.skip_rows > 0 || self.settings.skip_footer > 0 {
+            return Err(DataLoaderError::GenericError("skip_rows and skip_footer are not supported when reading in chunks".to_string()));
+        }
+
+        let mut records: CSVRecords<BufReader<R>> = CSVRecords::new(BufReader::new(reader), &self.settings);
+
+        let mut first_record: Option<Vec<String>> = None;
+        for record in records.by_ref() {
+            let record: Vec<String> = record?;
+            if !record.is_empty() {
+                first_record = Some(record);
+                break;
             }
+        }
+
+        let headers: Vec<String> = self.get_headers(first_record.as_ref())?;
+        let column_reader: ColumnReader = ColumnReader::new(&self.settings);
+        column_reader.check_headers(&headers)?;
+        let pending: Option<Vec<String>> = if self.settings.header_names.is_empty() { None } else { first_record };
+
+        Ok(CSVChunks {
+            records,
+            dtypes: vec![None; headers.len()],
+            headers,
+            pending,
+            chunk_size,
+            remaining_rows: self.settings.n_rows,
+            record_number: 0,
+            skip_blank_lines: self.settings.skip_blank_lines,
+            na_values: self.settings.na_values.clone(),
+            column_reader
+        })
+    }
+}
+
+impl<R: Read> CSVChunks<R> {
+    pub fn headers(&self) -> &[String] {
+        &self.headers
+    }
+
+    fn read_chunk(&mut self) -> Result<Option<DataMatrix>, DataLoaderError> {
+        let rows_to_read: usize = self.chunk_size.min(self.remaining_rows);
+        let mut raw_columns: Vec<Vec<Option<String>>> = vec![Vec::with_capacity(rows_to_read); self.headers.len()];
+        let mut index: Vec<String> = Vec::with_capacity(rows_to_read);
+
+        while index.len() < rows_to_read {
+            let record: Vec<String> = match self.pending.take() {
+                Some(record) => record,
+                None => match self.records.next() {
+                    Some(record) => record?,
+                    None => break
+                }
+            };
 
-            if let Some(parsed) = self.try_parse::<f64>(&values) {
-                columns.insert(
-                    header.clone(),
-                    DataVector {
-                        label: Some(header),
-                        data: Box::new(parsed),
-                        len: row_count,
-                        dtype: Some("f64"),
-                    },
-                );
-                continue;
+            let i: usize = self.record_number;
+            self.record_number += 1;
+
+            let fields: Vec<Option<String>> = if record.is_empty() {
+                if self.skip_blank_lines {
+                    continue;
+                } else {
+                    vec![None; self.headers.len()]
+                }
+            } else {
+                parse_fields(record, &self.na_values)
+            };
+            if fields.len() != self.headers.len() {
+                return Err(DataLoaderError::ColumnCountMismatch(format!("Record {} has {} fields but the header has {} columns", i, fields.len(), self.headers.len())));
             }
 
-            columns.insert(
-                header.clone(),
-                DataVector {
-                    label: Some(header),
-                    data: Box::new(values),
-                    len: row_count,
-                    dtype: Some("String"),
-                },
-            );
+            index.push(format!("{}", i));
+
+            for (column, field) in raw_columns.iter_mut().zip(fields) {
+                column.push(field);
+            }
         }
 
-        Ok(DataMatrix { columns, index })
+        if index.is_empty() {
+            return Ok(None);
+        }
+
+        self.remaining_rows -= index.len();
+
+        let mut columns: IndexMap<String, DataVector> = IndexMap::new();
+
+        for ((header, values), dtype) in self.headers.iter().zip(raw_columns).zip(self.dtypes.iter_mut()) {
+            columns.insert(header.clone(), Self::read_column(&self.column_reader, header, values, dtype)?);
+        }
+
+        Ok(Some(DataMatrix { columns, index }))
+    }
+
+    // Columns that have only held nulls so far are inferred again, and an i64 column is
+    // widened to f64 the first time a chunk fails to parse as i64 but parses as f64.
+    fn read_column(column_reader: &ColumnReader, header: &str, values: Vec<Option<String>>, dtype: &mut Option<DType>) -> Result<DataVector, DataLoaderError> {
+        if let Some(forced) = column_reader.forced_dtype(header) {
+            return column_reader.parse(header.to_string(), values, forced);
+        }
+
+        let Some(established) = *dtype else {
+            let has_values: bool = values.iter().any(Option::is_some);
+            let column: DataVector = column_reader.read(header.to_string(), values)?;
+            if has_values {
+                *dtype = Some(column.dtype());
+            }
+            return Ok(column);
+        };
+
+        let borrowed: Vec<Option<&str>> = values.iter().map(Option::as_deref).collect();
+        match column_reader.parse(header.to_string(), borrowed, established) {
+            Err(DataLoaderError::ParseError(_)) if established == DType::I64 => {
+                let column: DataVector = column_reader.parse(header.to_string(), values, DType::F64)?;
+                *dtype = Some(DType::F64);
+                Ok(column)
+            },
+            result => result
+        }
     }
-}
\ No newline at end of file
+}
+
+impl<R: Read> Iterator for CSVChunks<R> {
+    type Item = Result<DataMatrix, DataLoaderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_chunk().transpose()
+    }
+}
+
+#[async_trait]
+impl DataLoader for CSVLoader {
+    async fn load_from<'a>(&'a self, path: &'a str) -> Result<DataMatrix, DataLoaderError> {
+        let file: std::fs::File = std::fs::File::open(path).map_err(|e| DataLoaderError::FileRead(e.to_string()))?;
+        self.load_from_reader(file)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_chunks(input: &str, settings: CSVLoaderSettings) -> Result<Vec<DataMatrix>, DataLoaderError> {
+        CSVLoader::new(settings).chunks_from_reader(input.as_bytes(), 2)?.collect()
+    }
+
+    fn dtypes(chunk: &DataMatrix) -> Vec<DType> {
+        chunk.columns.values().map(DataVector::dtype).collect()
+    }
+
+    #[test]
+    fn chunks_widen_integers_and_infer_all_null_columns() {
+        let chunks: Vec<DataMatrix> = read_chunks("a,b\n1,\n2,\n3,4\n2.5,5\n4,6\n", CSVLoaderSettings::default()).unwrap();
+
+        assert_eq!(dtypes(&chunks[0]), vec![DType::I64, DType::Utf8]);
+        assert_eq!(dtypes(&chunks[1]), vec![DType::F64, DType::I64]);
+        assert_eq!(dtypes(&chunks[2]), vec![DType::F64, DType::I64]);
+        assert_eq!(chunks[1].get_column("a").unwrap().to_options::<f64>().unwrap(), vec![Some(3.0), Some(2.5)]);
+        assert_eq!(chunks[2].get_column("b").unwrap().to_options::<i64>().unwrap(), vec![Some(6)]);
+    }
+
+    #[test]
+    fn chunks_keep_inferred_dtype_for_later_nulls() {
+        let chunks: Vec<DataMatrix> = read_chunks("a\n1.5\n2\nNA\n\n", CSVLoaderSettings::default()).unwrap();
+
+        assert_eq!(dtypes(&chunks[0]), vec![DType::F64]);
+        assert_eq!(dtypes(&chunks[1]), vec![DType::F64]);
+        assert_eq!(chunks[1].get_column("a").unwrap().to_options::<f64>().unwrap(), vec![None]);
+    }
+
+    #[test]
+    fn chunks_reject_other_type_changes() {
+        let result: Result<Vec<DataMatrix>, DataLoaderError> = read_chunks("a\n1\n2\nx\n", CSVLoaderSettings::default());
+        assert!(matches!(result, Err(DataLoaderError::ParseError(_))));
+    }
+
+    #[test]
+    fn chunks_do_not_widen_forced_dtypes() {
+        let settings: CSVLoaderSettings = CSVLoaderSettings { dtypes: HashMap::from([("a".to_string(), DType::I64)]), ..Default::default() };
+        let result: Result<Vec<DataMatrix>, DataLoaderError> = read_chunks("a\n1\n2\n2.5\n", settings);
+        assert!(matches!(result, Err(DataLoaderError::ParseError(_))));
+    }
+
+    #[test]
+    fn na_values_are_read_as_nulls() {
+        let input: &str = "a,b\n1,NA\n,x\nnull,-\n";
+
+        let matrix: DataMatrix = CSVLoader::new(CSVLoaderSettings::default()).load_from_reader(input.as_bytes()).unwrap();
+        assert_eq!(matrix.get_column("a").unwrap().to_options::<i64>().unwrap(), vec![Some(1), None, None]);
+        assert_eq!(matrix.get_column("b").unwrap().to_options::<String>().unwrap(), vec![None, Some("x".to_string()), Some("-".to_string())]);
+
+        let settings: CSVLoaderSettings = CSVLoaderSettings { na_values: vec!["-".to_string()], ..Default::default() };
+        let matrix: DataMatrix = CSVLoader::new(settings).load_from_reader(input.as_bytes()).unwrap();
+        assert_eq!(matrix.get_column("a").unwrap().to_options::<String>().unwrap(), vec![Some("1".to_string()), Some(String::new()), Some("null".to_string())]);
+        assert_eq!(matrix.get_column("b").unwrap().to_options::<String>().unwrap(), vec![Some("NA".to_string()), Some("x".to_string()), None]);
+    }
+
+    #[test]
+    fn categorical_threshold_limits_distinct_text_values() {
+        let settings: CSVLoaderSettings = CSVLoaderSettings { categorical_threshold: Some(2), ..Default::default() };
+        let matrix: DataMatrix = CSVLoader::new(settings).load_from_reader("colour,name,n\nred,a,1\nblue,b,1\nred,c,1\nNA,d,1\n".as_bytes()).unwrap();
+
+        assert_eq!(dtypes(&matrix), vec![DType::Categorical, DType::Utf8, DType::I64]);
+        let colour: DataVector = matrix.get_column("colour").unwrap();
+        assert_eq!(colour.categories(), Some(["blue".to_string(), "red".to_string()].as_slice()));
+        assert_eq!(colour.null_count(), 1);
+    }
+}
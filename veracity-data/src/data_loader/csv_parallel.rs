@@ -0,0 +1,481 @@
+use std::{borrow::Cow, fs::File};
+
+use indexmap::IndexMap;
+use memmap2::Mmap;
+use rayon::prelude::*;
+
+use crate::{data_matrix::DataMatrix, data_vector::DataVector, enums::error_types::DataLoaderError};
+
+use super::csv_loader::{CSVLoader, CSVLoaderSettings, ColumnReader};
+
+// Chunks smaller than this are not worth handing to another thread.
+const MIN_CHUNK_BYTES: usize = 1 << 16;
+
+type Fields<'a> = Vec<Cow<'a, str>>;
+
+// Byte level version of CSVRecords for single byte separators and quote characters.
+// Fields borrow from the input and are only copied when a quoted field has to be
+// unescaped.
+struct RecordParser {
+    separator: u8,
+    quote_char: Option<u8>,
+    comment_prefix: Option<String>,
+    skip_initial_space: bool
+}
+
+struct ParsedChunk<'a> {
+    columns: Vec<Vec<Option<Cow<'a, str>>>>,
+    records: Vec<usize>,
+    record_count: usize
+}
+
+fn append<'a>(value: &mut Cow<'a, str>, piece: &'a str) {
+    if value.is_empty() {
+        *value = Cow::Borrowed(piece);
+    } else if !piece.is_empty() {
+        value.to_mut().push_str(piece);
+    }
+}
+
+fn ascii_byte(c: char, setting: &str) -> Result<u8, DataLoaderError> {
+    if c.is_ascii() {
+        Ok(c as u8)
+    } else {
+        Err(DataLoaderError::GenericError(format!("{} must be an ASCII character when loading in parallel", setting)))
+    }
+}
+
+impl RecordParser {
+    fn new(settings: &CSVLoaderSettings) -> Result<Self, DataLoaderError> {
+        Ok(RecordParser {
+            separator: ascii_byte(settings.separator, "separator")?,
+            quote_char: settings.quote_char.map(|c| ascii_byte(c, "quote_char")).transpose()?,
+            comment_prefix: settings.comment_prefix.clone().filter(|prefix| !prefix.is_empty()),
+            skip_initial_space: settings.skip_initial_space
+        })
+    }
+
+    fn is_comment(&self, bytes: &[u8], from: usize) -> bool {
+        self.comment_prefix.as_deref().is_some_and(|prefix| bytes[from..].starts_with(prefix.as_bytes()))
+    }
+
+    fn find_field_end(&self, bytes: &[u8], from: usize) -> usize {
+        bytes[from..].iter().position(|&b| b == self.separator || b == b'\n').map_or(bytes.len(), |i| from + i)
+    }
+
+    // Parses the record starting at pos into fields and moves pos past it. A blank line
+    // leaves fields empty, and a comment line is skipped and returns false.
+    fn parse_record<'a>(&self, text: &'a str, pos: &mut usize, fields: &mut Fields<'a>) -> Result<bool, DataLoaderError> {
+        let bytes: &[u8] = text.as_bytes();
+        fields.clear();
+
+        let line_end: usize = bytes[*pos..].iter().position(|&b| b == b'\n').map_or(bytes.len(), |i| *pos + i);
+        let is_comment: bool = self.is_comment(bytes, *pos);
+        if is_comment || text[*pos..line_end].trim().is_empty() {
+            *pos = (line_end + 1).min(bytes.len());
+            return Ok(!is_comment);
+        }
+
+        loop {
+            let start: usize = *pos;
+            let mut quote_start: usize = start;
+            if self.skip_initial_space {
+                while quote_start < bytes.len() && bytes[quote_start] != b'\n' && bytes[quote_start].is_ascii_whitespace() {
+                    quote_start += 1;
+                }
+            }
+
+            match self.quote_char {
+                Some(quote) if quote_start < bytes.len() && bytes[quote_start] == quote => {
+                    let mut value: Cow<'a, str> = Cow::Borrowed("");
+                    let mut i: usize = quote_start + 1;
+                    let mut segment_start: usize = i;
+
+                    loop {
+                        let closing: usize = bytes[i..]
+                            .iter()
+                            .position(|&b| b == quote)
+                            .map(|offset| i + offset)
+                            .ok_or(DataLoaderError::ParseError(format!("Unterminated quoted field starting at byte {}", quote_start)))?;
+
+                        if bytes.get(closing + 1) == Some(&quote) {
+                            append(&mut value, &text[segment_start..closing + 1]);
+                            i = closing + 2;
+                            segment_start = i;
+                        } else {
+                            append(&mut value, &text[segment_start..closing]);
+                            i = closing + 1;
+                            break;
+                        }
+                    }
+
+                    // Anything between the closing quote and the separator is kept as is.
+                    *pos = self.find_field_end(bytes, i);
+                    let trailing: &str = &text[i..*pos];
+                    append(&mut value, trailing.strip_suffix('\r').unwrap_or(trailing));
+
+                    if value.contains('\r') {
+                        value = Cow::Owned(value.replace("\r\n", "\n"));
+                    }
+                    fields.push(value);
+                },
+                _ => {
+                    *pos = self.find_field_end(bytes, start);
+                    fields.push(Cow::Borrowed(text[start..*pos].trim()));
+                }
+            }
+
+            if *pos >= bytes.len() {
+                return Ok(true);
+            }
+
+            *pos += 1;
+            if bytes[*pos - 1] == b'\n' {
+                return Ok(true);
+            }
+        }
+    }
+
+    // Calls on_record_end with the offset just past each line break that ends a record,
+    // until it returns false. Quoting is tracked the same way parse_record does, so line
+    // breaks inside quoted fields are skipped, and comment lines are passed over whole.
+    fn scan_records(&self, bytes: &[u8], start: usize, mut on_record_end: impl FnMut(usize) -> bool) {
+        let mut at_field_start: bool = true;
+        let mut in_quotes: bool = false;
+        let mut i: usize = start;
+
+        while i < bytes.len() {
+            let b: u8 = bytes[i];
+
+            if !in_quotes && (i == start || bytes[i - 1] == b'\n') && self.is_comment(bytes, i) {
+                match bytes[i..].iter().position(|&b| b == b'\n') {
+                    Some(offset) => i += offset,
+                    None => return
+                }
+                continue;
+            }
+
+            if in_quotes {
+                if Some(b) == self.quote_char {
+                    if bytes.get(i + 1) == Some(&b) {
+                        i += 1;
+                    } else {
+                        in_quotes = false;
+                    }
+                }
+            } else if b == b'\n' {
+                at_field_start = true;
+                if !on_record_end(i + 1) {
+                    return;
+                }
+            } else if b == self.separator {
+                at_field_start = true;
+            } else if Some(b) == self.quote_char && at_field_start {
+                in_quotes = true;
+                at_field_start = false;
+            } else if !(self.skip_initial_space && b.is_ascii_whitespace()) {
+                at_field_start = false;
+            }
+
+            i += 1;
+        }
+    }
+
+    fn record_end(&self, bytes: &[u8], start: usize) -> usize {
+        let mut end: usize = bytes.len();
+        self.scan_records(bytes, start, |record_end| {
+            end = record_end;
+            false
+        });
+        end
+    }
+
+    // Offsets splitting bytes[start..] into pieces of roughly step bytes, each ending on
+    // a record boundary.
+    fn record_boundaries(&self, bytes: &[u8], start: usize, step: usize) -> Vec<usize> {
+        let mut boundaries: Vec<usize> = vec![start];
+        let mut next_target: usize = start + step;
+
+        self.scan_records(bytes, start, |record_end| {
+            if record_end >= next_target && record_end < bytes.len() {
+                boundaries.push(record_end);
+                next_target = record_end + step;
+            }
+            true
+        });
+
+        boundaries.push(bytes.len());
+        boundaries
+    }
+
+    fn parse_chunk<'a>(&self, text: &'a str, offset: usize, num_columns: usize, settings: &CSVLoaderSettings) -> Result<ParsedChunk<'a>, DataLoaderError> {
+        let mut chunk: ParsedChunk<'a> = ParsedChunk {
+            columns: (0..num_columns).map(|_| Vec::new()).collect(),
+            records: Vec::new(),
+            record_count: 0
+        };
+        let mut fields: Fields<'a> = Vec::with_capacity(num_columns);
+        let mut pos: usize = 0;
+
+        while pos < text.len() {
+            let record_start: usize = pos;
+            if !self.parse_record(text, &mut pos, &mut fields)? {
+                continue;
+            }
+
+            let record: usize = chunk.record_count;
+            chunk.record_count += 1;
+
+            if fields.is_empty() {
+                if settings.skip_blank_lines {
+                    continue;
+                }
+                chunk.columns.iter_mut().for_each(|column| column.push(None));
+            } else if fields.len() != num_columns {
+                return Err(DataLoaderError::ColumnCountMismatch(format!("The record at byte {} has {} fields but the header has {} columns", offset + record_start, fields.len(), num_columns)));
+            } else {
+                for (column, field) in chunk.columns.iter_mut().zip(fields.drain(..)) {
+                    column.push(if settings.na_values.iter().any(|na| *na == field) { None } else { Some(field) });
+                }
+            }
+
+            chunk.records.push(record);
+        }
+
+        Ok(chunk)
+    }
+}
+
+impl CSVLoader {
+    // High throughput alternative to load_from for large files. The file is memory
+    // mapped, split at record boundaries and the pieces are parsed and typed on the rayon
+    // thread pool. Escape characters, skip_rows, skip_footer and header_indices are not
+    // supported on this path. The file must not be modified while it is being loaded.
+    pub async fn load_parallel(&self, path: &str) -> Result<DataMatrix, DataLoaderError> {
+        let file: File = File::open(path).map_err(|e| DataLoaderError::FileRead(e.to_string()))?;
+
+        // SAFETY: the mapping is read-only, but another process can still truncate or
+        // rewrite the file underneath it, which would change the bytes being parsed or
+        // fault on access. That is the documented contract of load_parallel; callers who
+        // cannot rule it out should use load_from.
+        let mapped: Mmap = unsafe { Mmap::map(&file) }.map_err(|e| DataLoaderError::FileRead(e.to_string()))?;
+
+        #[cfg(unix)]
+        mapped.advise(memmap2::Advice::Sequential).map_err(|e| DataLoaderError::FileRead(e.to_string()))?;
+
+        self.load_parallel_from_bytes(&mapped)
+    }
+
+    pub fn load_parallel_from_bytes(&self, bytes: &[u8]) -> Result<DataMatrix, DataLoaderError> {
+        if self.settings.escape_char.is_some() {
+            return Err(DataLoaderError::GenericError("escape_char is not supported when loading in parallel".to_string()));
+        }
+        if self.settings.skip_rows > 0 || self.settings.skip_footer > 0 || !self.settings.header_indices.is_empty() {
+            return Err(DataLoaderError::GenericError("skip_rows, skip_footer and header_indices are not supported when loading in parallel".to_string()));
+        }
+
+        let parser: RecordParser = RecordParser::new(&self.settings)?;
+        let utf8_error = |e: std::str::Utf8Error| DataLoaderError::ParseError(e.to_string());
+
+        // Line breaks are always character boundaries, so the header and every chunk can
+        // be validated as UTF-8 on their own.
+        let mut data_start: usize = 0;
+        let mut first_record: Option<Vec<String>> = None;
+        let mut fields: Fields = Vec::new();
+
+        while data_start < bytes.len() {
+            let end: usize = parser.record_end(bytes, data_start);
+            let text: &str = std::str::from_utf8(&bytes[data_start..end]).map_err(utf8_error)?;
+
+            let mut pos: usize = 0;
+            parser.parse_record(text, &mut pos, &mut fields)?;
+            data_start = end;
+
+            if !fields.is_empty() {
+                first_record = Some(fields.iter().map(|field| field.to_string()).collect());
+                break;
+            }
+        }
+
+        // With header_names every record is data, as in load_from.
+        if !self.settings.header_names.is_empty() {
+            data_start = 0;
+        }
+
+        let headers: Vec<String> = self.get_headers(first_record.as_ref())?;
+        let column_reader: ColumnReader = ColumnReader::new(&self.settings);
+        column_reader.check_headers(&headers)?;
+
+        let step: usize = ((bytes.len() - data_start) / (rayon::current_num_threads() * 4)).max(MIN_CHUNK_BYTES);
+        let boundaries: Vec<usize> = parser.record_boundaries(bytes, data_start, step);
+
+        let chunks: Vec<ParsedChunk> = boundaries
+            .par_windows(2)
+            .map(|range| {
+                let text: &str = std::str::from_utf8(&bytes[range[0]..range[1]]).map_err(utf8_error)?;
+                parser.parse_chunk(text, range[0], headers.len(), &self.settings)
+            })
+            .collect::<Result<Vec<ParsedChunk>, DataLoaderError>>()?;
+
+        // Index labels count every record after the header, blank ones included, as
+        // load_from does.
+        let mut index: Vec<String> = Vec::new();
+        let mut records_before: usize = 0;
+        for chunk in chunks.iter() {
+            index.extend(chunk.records.iter().map(|record| format!("{}", records_before + record)));
+            records_before += chunk.record_count;
+        }
+        index.truncate(self.settings.n_rows);
+
+        let mut raw_columns: Vec<Vec<Option<Cow<str>>>> = (0..headers.len()).map(|_| Vec::with_capacity(index.len())).collect();
+        for mut chunk in chunks {
+            for (raw_column, column) in raw_columns.iter_mut().zip(chunk.columns.iter_mut()) {
+                raw_column.append(column);
+            }
+        }
+
+        let columns: Vec<DataVector> = raw_columns
+            .into_par_iter()
+            .zip(headers.par_iter())
+            .map(|(mut values, header)| {
+                values.truncate(self.settings.n_rows);
+                column_reader.read(header.clone(), values)
+            })
+            .collect::<Result<Vec<DataVector>, DataLoaderError>>()?;
+
+        let columns: IndexMap<String, DataVector> = headers.into_iter().zip(columns).collect();
+
+        Ok(DataMatrix { columns, index })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data_loader::csv_records::CSVRecords;
+
+    use super::*;
+
+    async fn load_both(name: &str, input: &str, settings: CSVLoaderSettings) -> (Result<DataMatrix, DataLoaderError>, Result<DataMatrix, DataLoaderError>) {
+        let path: std::path::PathBuf = std::env::temp_dir().join(format!("veracity_parallel_{}_{}.csv", std::process::id(), name));
+        std::fs::write(&path, input).unwrap();
+
+        let loader: CSVLoader = CSVLoader::new(settings);
+        let parallel: Result<DataMatrix, DataLoaderError> = loader.load_parallel(path.to_str().unwrap()).await;
+        std::fs::remove_file(&path).unwrap();
+
+        (parallel, loader.load_from_reader(input.as_bytes()))
+    }
+
+    async fn assert_parity(name: &str, input: &str, settings: CSVLoaderSettings) {
+        let (parallel, sequential) = load_both(name, input, settings).await;
+        let (parallel, sequential) = (parallel.unwrap(), sequential.unwrap());
+
+        assert_eq!(parallel.index, sequential.index, "{}", name);
+        assert_eq!(parallel.column_names(), sequential.column_names(), "{}", name);
+        for (column, expected) in parallel.columns.values().zip(sequential.columns.values()) {
+            assert_eq!(column.data, expected.data, "{}", name);
+            assert_eq!(column.validity, expected.validity, "{}", name);
+        }
+    }
+
+    #[tokio::test]
+    async fn parallel_load_matches_load_from_reader() {
+        assert_parity("quoted_newlines", "a,b\n1,\"x\ny\"\n2,\"say \"\"z\"\"\"\n", CSVLoaderSettings::default()).await;
+        assert_parity("crlf", "a,b\r\n1,x\r\n\r\n2,\"y\r\nz\"\r\n", CSVLoaderSettings::default()).await;
+        assert_parity("blank_lines", "a,b\n1,x\n\n2,y\n", CSVLoaderSettings { skip_blank_lines: false, ..Default::default() }).await;
+        assert_parity("n_rows", "a,b\n1,x\n2,y\n3,z\n", CSVLoaderSettings { n_rows: 2, ..Default::default() }).await;
+        assert_parity(
+            "comments",
+            "# leading comment\na,b\n1,\"x\n# kept\"\n# dropped, \"with a quote\n2,y\n",
+            CSVLoaderSettings { comment_prefix: Some("#".to_string()), ..Default::default() }
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn parallel_load_matches_on_empty_files() {
+        let (parallel, sequential) = load_both("empty", "", CSVLoaderSettings::default()).await;
+        assert!(matches!(parallel, Err(DataLoaderError::GenericError(_))));
+        assert!(matches!(sequential, Err(DataLoaderError::GenericError(_))));
+    }
+
+    #[tokio::test]
+    async fn parallel_load_rejects_skip_rows() {
+        let (parallel, _) = load_both("skip_rows", "a\n1\n2\n", CSVLoaderSettings { skip_rows: 1, ..Default::default() }).await;
+        assert!(matches!(parallel, Err(DataLoaderError::GenericError(_))));
+    }
+
+    fn parallel_records(input: &str, settings: &CSVLoaderSettings) -> Result<Vec<Vec<String>>, DataLoaderError> {
+        let parser: RecordParser = RecordParser::new(settings)?;
+        let mut records: Vec<Vec<String>> = Vec::new();
+        let mut fields: Fields = Vec::new();
+        let mut pos: usize = 0;
+
+        while pos < input.len() {
+            if parser.parse_record(input, &mut pos, &mut fields)? {
+                records.push(fields.iter().map(|field| field.to_string()).collect());
+            }
+        }
+
+        Ok(records)
+    }
+
+    fn sequential_records(input: &str, settings: &CSVLoaderSettings) -> Result<Vec<Vec<String>>, DataLoaderError> {
+        CSVRecords::new(input.as_bytes(), settings).collect()
+    }
+
+    // Name, input, settings and the expected records, or None for a ParseError.
+    type ParserCase = (&'static str, &'static str, CSVLoaderSettings, Option<Vec<Vec<&'static str>>>);
+
+    // Each case runs against both RecordParser and CSVRecords.
+    #[test]
+    fn parsers_split_records_alike() {
+        let comments: CSVLoaderSettings = CSVLoaderSettings { comment_prefix: Some("#".to_string()), ..Default::default() };
+        let literal_spaces: CSVLoaderSettings = CSVLoaderSettings { skip_initial_space: false, ..Default::default() };
+        let single_quotes: CSVLoaderSettings = CSVLoaderSettings { separator: ';', quote_char: Some('\''), ..Default::default() };
+
+        let cases: Vec<ParserCase> = vec![
+            ("doubled quotes", "a,\"say \"\"hi\"\"\"\n", CSVLoaderSettings::default(), Some(vec![vec!["a", "say \"hi\""]])),
+            ("separators and line breaks", "\"x,y\",\"one\r\ntwo\"\r\nb,c\r\n", CSVLoaderSettings::default(), Some(vec![vec!["x,y", "one\ntwo"], vec!["b", "c"]])),
+            ("custom quote char", "'a;b';'it''s';\"c\"\n", single_quotes, Some(vec![vec!["a;b", "it's", "\"c\""]])),
+            ("comments", "# header comment\na,\"b\n# not a comment\"\n#trailing\n", comments, Some(vec![vec!["a", "b\n# not a comment"]])),
+            ("skip_initial_space", "a, \"b,c\"\n", CSVLoaderSettings::default(), Some(vec![vec!["a", "b,c"]])),
+            ("literal initial space", "a, \"b,c\"\n", literal_spaces, Some(vec![vec!["a", "\"b", "c\""]])),
+            ("blank lines", "a\n\n  \nb", CSVLoaderSettings::default(), Some(vec![vec!["a"], vec![], vec![], vec!["b"]])),
+            ("unterminated quote", "a,b\nc,\"open\nstill open\n", CSVLoaderSettings::default(), None)
+        ];
+
+        for (name, input, settings, expected) in cases {
+            for parsed in [parallel_records(input, &settings), sequential_records(input, &settings)] {
+                match &expected {
+                    Some(expected) => assert_eq!(parsed.unwrap(), *expected, "{}", name),
+                    None => assert!(matches!(parsed, Err(DataLoaderError::ParseError(_))), "{}", name)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn escape_char_and_non_ascii_settings_are_rejected() {
+        let escaped: CSVLoaderSettings = CSVLoaderSettings { escape_char: Some('\\'), ..Default::default() };
+        assert!(matches!(CSVLoader::new(escaped).load_parallel_from_bytes(b"a\n1\n"), Err(DataLoaderError::GenericError(_))));
+
+        let non_ascii: CSVLoaderSettings = CSVLoaderSettings { separator: '§', ..Default::default() };
+        assert!(RecordParser::new(&non_ascii).is_err());
+    }
+
+    #[test]
+    fn parallel_chunks_split_at_record_boundaries() {
+        let row: &str = "1,\"two\nlines\",# not a comment\n";
+        let input: String = format!("a,b,c\n{}", row.repeat(MIN_CHUNK_BYTES / row.len() * 3));
+        let settings: CSVLoaderSettings = CSVLoaderSettings { comment_prefix: Some("#".to_string()), ..Default::default() };
+
+        let loader: CSVLoader = CSVLoader::new(settings);
+        let parallel: DataMatrix = loader.load_parallel_from_bytes(input.as_bytes()).unwrap();
+        let sequential: DataMatrix = loader.load_from_reader(input.as_bytes()).unwrap();
+
+        assert_eq!(parallel.index, sequential.index);
+        assert_eq!(parallel.columns["b"].data, sequential.columns["b"].data);
+        assert_eq!(parallel.columns["c"].data, sequential.columns["c"].data);
+    }
+}
@@ -0,0 +1,171 @@
+use std::{io::BufRead, iter::Peekable, str::Chars};
+
+use crate::enums::error_types::DataLoaderError;
+
+use super::csv_loader::CSVLoaderSettings;
+
+// Splits RFC 4180 records out of a buffered reader. Quoted fields may contain the
+// separator, line breaks and doubled quote characters; an optional escape character
+// takes the following character literally. A blank line yields an empty record.
+pub(crate) struct CSVRecords<R: BufRead> {
+    reader: R,
+    separator: char,
+    quote_char: Option<char>,
+    escape_char: Option<char>,
+    comment_prefix: Option<String>,
+    skip_initial_space: bool,
+    line: String,
+    line_number: usize
+}
+
+impl<R: BufRead> CSVRecords<R> {
+    pub(crate) fn new(reader: R, settings: &CSVLoaderSettings) -> Self {
+        CSVRecords {
+            reader,
+            separator: settings.separator,
+            quote_char: settings.quote_char,
+            escape_char: settings.escape_char.filter(|&escape| Some(escape) != settings.quote_char),
+            comment_prefix: settings.comment_prefix.clone().filter(|prefix| !prefix.is_empty()),
+            skip_initial_space: settings.skip_initial_space,
+            line: String::new(),
+            line_number: 0
+        }
+    }
+
+    fn read_line(&mut self) -> Result<bool, DataLoaderError> {
+        self.line.clear();
+        let bytes: usize = self.reader.read_line(&mut self.line).map_err(|e| DataLoaderError::FileRead(e.to_string()))?;
+        if bytes == 0 {
+            return Ok(false);
+        }
+
+        self.line_number += 1;
+        if self.line.ends_with('\n') {
+            self.line.pop();
+            if self.line.ends_with('\r') {
+                self.line.pop();
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn finish_field(&self, fields: &mut Vec<String>, field: &mut String, quoted: bool) {
+        let value: String = std::mem::take(field);
+        if quoted {
+            fields.push(value);
+        } else {
+            fields.push(value.trim().to_string());
+        }
+    }
+
+    fn next_record(&mut self) -> Result<Option<Vec<String>>, DataLoaderError> {
+        loop {
+            if !self.read_line()? {
+                return Ok(None);
+            }
+
+            if let Some(prefix) = self.comment_prefix.as_deref() && self.line.starts_with(prefix) {
+                continue;
+            }
+
+            break;
+        }
+
+        if self.line.trim().is_empty() {
+            return Ok(Some(Vec::new()));
+        }
+
+        let start_line: usize = self.line_number;
+        let mut fields: Vec<String> = Vec::new();
+        let mut field: String = String::new();
+        let mut quoted: bool = false;
+        let mut in_quotes: bool = false;
+
+        loop {
+            let line: String = std::mem::take(&mut self.line);
+            let mut chars: Peekable<Chars> = line.chars().peekable();
+            let mut escaped_newline: bool = false;
+
+            while let Some(c) = chars.next() {
+                if Some(c) == self.escape_char {
+                    match chars.next() {
+                        Some(escaped) => field.push(escaped),
+                        None => {
+                            field.push('\n');
+                            escaped_newline = true;
+                        }
+                    }
+                } else if in_quotes {
+                    if Some(c) == self.quote_char {
+                        if chars.peek() == Some(&c) {
+                            field.push(c);
+                            chars.next();
+                        } else {
+                            in_quotes = false;
+                        }
+                    } else {
+                        field.push(c);
+                    }
+                } else if c == self.separator {
+                    self.finish_field(&mut fields, &mut field, quoted);
+                    quoted = false;
+                } else if Some(c) == self.quote_char && !quoted && (field.is_empty() || (self.skip_initial_space && field.trim().is_empty())) {
+                    field.clear();
+                    quoted = true;
+                    in_quotes = true;
+                } else {
+                    field.push(c);
+                }
+            }
+
+            if !in_quotes && !escaped_newline {
+                break;
+            }
+
+            if !escaped_newline {
+                field.push('\n');
+            }
+
+            if !self.read_line()? {
+                return Err(DataLoaderError::ParseError(format!("Unterminated quoted field in the record starting on line {}", start_line)));
+            }
+        }
+
+        self.finish_field(&mut fields, &mut field, quoted);
+
+        Ok(Some(fields))
+    }
+}
+
+impl<R: BufRead> Iterator for CSVRecords<R> {
+    type Item = Result<Vec<String>, DataLoaderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_record().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Cases shared with RecordParser are in csv_parallel's tests; these cover what only
+    // this parser supports.
+    fn records(input: &str, settings: &CSVLoaderSettings) -> Result<Vec<Vec<String>>, DataLoaderError> {
+        CSVRecords::new(input.as_bytes(), settings).collect()
+    }
+
+    #[test]
+    fn custom_quote_and_escape_chars() {
+        let settings: CSVLoaderSettings = CSVLoaderSettings { separator: ';', quote_char: Some('\''), escape_char: Some('\\'), ..Default::default() };
+        let parsed: Vec<Vec<String>> = records("'a;b';c\\;d;\"e\"\nline\\\nbreak;x;y\n", &settings).unwrap();
+        assert_eq!(parsed, vec![vec!["a;b", "c;d", "\"e\""], vec!["line\nbreak", "x", "y"]]);
+    }
+
+    #[test]
+    fn unterminated_quote_reports_its_line() {
+        let result: Result<Vec<Vec<String>>, DataLoaderError> = records("a,b\nc,\"open\nstill open\n", &CSVLoaderSettings::default());
+        assert!(matches!(result, Err(DataLoaderError::ParseError(message)) if message.contains("line 2")));
+    }
+}
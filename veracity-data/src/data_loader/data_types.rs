@@ -1,8 +1,36 @@
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum DataTypes {
     CSV,
     Excel,
     JSON,
     XML,
     SQL,
-    ZIP
-}
\ No newline at end of file
+    ZIP,
+    Parquet,
+    Arrow
+}
+
+impl DataTypes {
+    pub fn from_extension(extension: &str) -> Option<DataTypes> {
+        match extension.to_lowercase().as_str() {
+            "csv" | "tsv" | "txt" => Some(DataTypes::CSV),
+            "xlsx" | "xlsm" | "xlsb" | "xls" | "ods" => Some(DataTypes::Excel),
+            "json" | "ndjson" | "jsonl" => Some(DataTypes::JSON),
+            "xml" => Some(DataTypes::XML),
+            "db" | "sqlite" | "sqlite3" => Some(DataTypes::SQL),
+            "zip" | "gz" | "zst" | "zstd" => Some(DataTypes::ZIP),
+            "parquet" => Some(DataTypes::Parquet),
+            "arrow" | "feather" | "ipc" | "arrows" => Some(DataTypes::Arrow),
+            _ => None
+        }
+    }
+
+    pub fn from_path(path: &str) -> Option<DataTypes> {
+        Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(DataTypes::from_extension)
+    }
+}
@@ -0,0 +1,229 @@
+use async_trait::async_trait;
+use calamine::{open_workbook_auto, Data, Range, Reader};
+use chrono::NaiveDate;
+use indexmap::IndexMap;
+use veracity_types::dtype::DType;
+
+use crate::{data_matrix::DataMatrix, data_vector::DataVector, enums::error_types::DataLoaderError, temporal::{default_date_formats, parse_datetime}};
+
+use super::{data_loader_settings::DataLoaderSettings, DataLoader};
+
+pub struct ExcelLoader {
+    pub settings: ExcelLoaderSettings
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExcelSheet {
+    Name(String),
+    Index(usize)
+}
+
+pub struct ExcelLoaderSettings {
+    pub sheet: ExcelSheet,
+    pub header_names: Vec<String>,
+    pub index_col: Option<usize>,
+    pub skip_rows: usize,
+    pub n_rows: usize,
+    pub skip_blank_lines: bool
+}
+
+impl Default for ExcelLoaderSettings {
+    fn default() -> Self {
+        Self {
+            sheet: ExcelSheet::Index(0),
+            header_names: Vec::new(),
+            index_col: None,
+            skip_rows: 0,
+            n_rows: usize::MAX,
+            skip_blank_lines: true
+        }
+    }
+}
+
+impl DataLoaderSettings for ExcelLoaderSettings {}
+
+impl ExcelLoader {
+    pub fn new(settings: ExcelLoaderSettings) -> Self {
+        ExcelLoader {
+            settings
+        }
+    }
+
+    fn read_sheet(&self, path: &str) -> Result<Range<Data>, DataLoaderError> {
+        let mut workbook = open_workbook_auto(path).map_err(|e| DataLoaderError::FileRead(e.to_string()))?;
+
+        match &self.settings.sheet {
+            ExcelSheet::Name(name) => workbook
+                .worksheet_range(name)
+                .map_err(|e| DataLoaderError::ParseError(e.to_string())),
+            ExcelSheet::Index(index) => workbook
+                .worksheet_range_at(*index)
+                .ok_or(DataLoaderError::GenericError(format!("Workbook has no sheet at index {}", index)))?
+                .map_err(|e| DataLoaderError::ParseError(e.to_string()))
+        }
+    }
+
+    fn get_headers(&self, first_row: &[Data]) -> Result<Vec<String>, DataLoaderError> {
+        if !self.settings.header_names.is_empty() {
+            if first_row.len() != self.settings.header_names.len() {
+                return Err(DataLoaderError::ColumnCountMismatch(format!("header_names property had {} values and the sheet had {} columns", self.settings.header_names.len(), first_row.len())));
+            }
+            return Ok(self.settings.header_names.clone());
+        }
+
+        Ok(first_row
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| match cell {
+                Data::Empty => i.to_string(),
+                cell => cell.to_string().trim().to_string()
+            })
+            .collect())
+    }
+
+    // Empty cells give None; error cells such as #DIV/0! are reported rather than loaded.
+    fn cell_to_string(cell: &Data) -> Result<Option<String>, DataLoaderError> {
+        Ok(Some(match cell {
+            Data::DateTime(dt) if dt.is_datetime() => {
+                let (year, month, day, hour, minute, second, milli) = dt.to_ymd_hms_milli();
+                if milli == 0 {
+                    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}", year, month, day, hour, minute, second)
+                } else {
+                    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}", year, month, day, hour, minute, second, milli)
+                }
+            },
+            Data::Empty => return Ok(None),
+            Data::Error(e) => return Err(DataLoaderError::ParseError(format!("Cell contains the error {}", e))),
+            cell => cell.to_string()
+        }))
+    }
+
+    // Microseconds since the Unix epoch for date cells and ISO 8601 date strings.
+    fn cell_to_micros(cell: &Data, date_formats: &[String]) -> Option<i64> {
+        match cell {
+            Data::DateTime(dt) if dt.is_datetime() => {
+                let (year, month, day, hour, minute, second, milli) = dt.to_ymd_hms_milli();
+                NaiveDate::from_ymd_opt(year as i32, month as u32, day as u32)
+                    .and_then(|date| date.and_hms_milli_opt(hour as u32, minute as u32, second as u32, milli as u32))
+                    .map(|datetime| datetime.and_utc().timestamp_micros())
+            },
+            Data::DateTimeIso(value) => parse_datetime(value, date_formats),
+            _ => None
+        }
+    }
+
+    // Empty cells become nulls in the column.
+    fn build_column(header: String, cells: Vec<&Data>) -> Result<DataVector, DataLoaderError> {
+        if let Some(row) = cells.iter().position(|c| matches!(c, Data::Error(_))) {
+            return Err(DataLoaderError::ParseError(format!("Column '{}' has an error cell in row {}: {}", header, row, cells[row])));
+        }
+
+        let present: Vec<&Data> = cells.iter().copied().filter(|c| !matches!(c, Data::Empty)).collect();
+
+        if !present.is_empty() && present.iter().all(|c| matches!(c, Data::Bool(_))) {
+            let parsed: Vec<Option<bool>> = cells.iter().map(|c| if let Data::Bool(v) = c { Some(*v) } else { None }).collect();
+            return Ok(DataVector::from_nullable(Some(header), parsed));
+        }
+
+        if !present.is_empty() && present.iter().all(|c| matches!(c, Data::Int(_))) {
+            let parsed: Vec<Option<i64>> = cells.iter().map(|c| if let Data::Int(v) = c { Some(*v) } else { None }).collect();
+            return Ok(DataVector::from_nullable(Some(header), parsed));
+        }
+
+        let date_formats: Vec<String> = default_date_formats();
+        if !present.is_empty() && present.iter().all(|c| Self::cell_to_micros(c, &date_formats).is_some()) {
+            let parsed: Vec<Option<i64>> = cells.iter().map(|c| Self::cell_to_micros(c, &date_formats)).collect();
+            return Ok(DataVector::from_micros(Some(header), parsed, DType::Datetime));
+        }
+
+        if !present.is_empty() && present.iter().all(|c| matches!(c, Data::Int(_) | Data::Float(_))) {
+            let parsed: Vec<Option<f64>> = cells
+                .iter()
+                .map(|c| match c {
+                    Data::Int(v) => Some(*v as f64),
+                    Data::Float(v) => Some(*v),
+                    _ => None
+                })
+                .collect();
+            return Ok(DataVector::from_nullable(Some(header), parsed));
+        }
+
+        let parsed: Vec<Option<String>> = cells
+            .iter()
+            .map(|c| Self::cell_to_string(c))
+            .collect::<Result<Vec<Option<String>>, DataLoaderError>>()?;
+
+        Ok(DataVector::from_nullable(Some(header), parsed))
+    }
+}
+
+#[async_trait]
+impl DataLoader for ExcelLoader {
+    async fn load_from<'a>(&'a self, path: &'a str) -> Result<DataMatrix, DataLoaderError> {
+        let range: Range<Data> = self.read_sheet(path)?;
+
+        let mut rows = range.rows().skip(self.settings.skip_rows).peekable();
+        let first_row: &[Data] = rows.peek().ok_or(DataLoaderError::GenericError("Sheet contains no data".to_string()))?;
+
+        let headers: Vec<String> = self.get_headers(first_row)?;
+
+        if self.settings.index_col.is_some_and(|col| col >= headers.len()) {
+            return Err(DataLoaderError::ColumnCountMismatch(format!("index_col was out of range for a sheet with {} columns", headers.len())));
+        }
+
+        if self.settings.header_names.is_empty() {
+            rows.next();
+        }
+
+        let data_rows: Vec<&[Data]> = rows
+            .filter(|row| !(self.settings.skip_blank_lines && row.iter().all(|c| matches!(c, Data::Empty))))
+            .take(self.settings.n_rows)
+            .collect();
+
+        let mut columns: IndexMap<String, DataVector> = IndexMap::new();
+        let mut index: Vec<String> = (0..data_rows.len()).map(|i| format!("{}", i)).collect();
+
+        for (col_idx, header) in headers.into_iter().enumerate() {
+            let cells: Vec<&Data> = data_rows.iter().map(|row| &row[col_idx]).collect();
+
+            if self.settings.index_col == Some(col_idx) {
+                index = cells
+                    .iter()
+                    .map(|c| Self::cell_to_string(c)?.ok_or(DataLoaderError::NullValues(header.clone())))
+                    .collect::<Result<Vec<String>, DataLoaderError>>()?;
+                continue;
+            }
+
+            if columns.contains_key(&header) {
+                return Err(DataLoaderError::DuplicateLabel);
+            }
+
+            columns.insert(header.clone(), Self::build_column(header, cells)?);
+        }
+
+        let mut matrix: DataMatrix = DataMatrix { columns, index: Vec::new() };
+        matrix.set_index(index.iter().map(String::as_str).collect())?;
+
+        Ok(matrix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use calamine::{ExcelDateTime, ExcelDateTimeType};
+
+    use super::*;
+
+    #[test]
+    fn date_cells_load_as_datetimes() {
+        let cells: Vec<Data> = vec![
+            Data::DateTime(ExcelDateTime::new(45293.5, ExcelDateTimeType::DateTime, false)),
+            Data::DateTimeIso("2024-01-03T08:30:00".to_string()),
+            Data::Empty
+        ];
+        let column: DataVector = ExcelLoader::build_column("when".to_string(), cells.iter().collect()).unwrap();
+
+        assert_eq!(column.dtype(), DType::Datetime);
+        assert_eq!(column.to_options::<i64>().unwrap(), vec![Some(1_704_196_800_000_000), Some(1_704_270_600_000_000), None]);
+    }
+}
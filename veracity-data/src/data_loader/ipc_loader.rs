@@ -0,0 +1,122 @@
+use std::{fs::File, sync::Arc};
+
+use arrow_array::RecordBatch;
+use arrow_ipc::{reader::{FileReader, StreamReader}, writer::{FileWriter, StreamWriter}};
+use arrow_schema::{Schema, SchemaRef};
+use async_trait::async_trait;
+
+use crate::{data_matrix::{DataMatrix, INDEX_COLUMN}, enums::error_types::DataLoaderError};
+
+use super::{data_loader_settings::DataLoaderSettings, DataLoader};
+
+pub struct ArrowIPCLoader {
+    pub settings: ArrowIPCLoaderSettings
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ArrowIPCFormat {
+    File,
+    Stream
+}
+
+#[derive(Clone)]
+pub struct ArrowIPCLoaderSettings {
+    pub format: ArrowIPCFormat,
+    pub columns: Vec<String>,
+    pub write_index: bool
+}
+
+impl Default for ArrowIPCLoaderSettings {
+    fn default() -> Self {
+        Self {
+            format: ArrowIPCFormat::File,
+            columns: Vec::new(),
+            write_index: true
+        }
+    }
+}
+
+impl DataLoaderSettings for ArrowIPCLoaderSettings {}
+
+impl ArrowIPCLoader {
+    pub fn new(settings: ArrowIPCLoaderSettings) -> Self {
+        ArrowIPCLoader {
+            settings
+        }
+    }
+
+    pub async fn write_to(&self, matrix: &DataMatrix, path: &str) -> Result<(), DataLoaderError> {
+        let batch: RecordBatch = matrix.to_record_batch(self.settings.write_index)?;
+        let file: File = File::create(path).map_err(|e| DataLoaderError::FileWrite(e.to_string()))?;
+
+        match self.settings.format {
+            ArrowIPCFormat::File => {
+                let mut writer: FileWriter<File> = FileWriter::try_new(file, &batch.schema()).map_err(|e| DataLoaderError::GenericError(e.to_string()))?;
+                writer.write(&batch).map_err(|e| DataLoaderError::FileWrite(e.to_string()))?;
+                writer.finish().map_err(|e| DataLoaderError::FileWrite(e.to_string()))
+            },
+            ArrowIPCFormat::Stream => {
+                let mut writer: StreamWriter<File> = StreamWriter::try_new(file, &batch.schema()).map_err(|e| DataLoaderError::GenericError(e.to_string()))?;
+                writer.write(&batch).map_err(|e| DataLoaderError::FileWrite(e.to_string()))?;
+                writer.finish().map_err(|e| DataLoaderError::FileWrite(e.to_string()))
+            }
+        }
+    }
+
+    fn open(path: &str) -> Result<File, DataLoaderError> {
+        File::open(path).map_err(|e| DataLoaderError::FileRead(e.to_string()))
+    }
+
+    fn read_schema(&self, path: &str) -> Result<SchemaRef, DataLoaderError> {
+        match self.settings.format {
+            ArrowIPCFormat::File => FileReader::try_new(Self::open(path)?, None).map(|reader| reader.schema()),
+            ArrowIPCFormat::Stream => StreamReader::try_new(Self::open(path)?, None).map(|reader| reader.schema())
+        }
+        .map_err(|e| DataLoaderError::ParseError(e.to_string()))
+    }
+
+    fn projection(&self, schema: &Schema) -> Result<Option<Vec<usize>>, DataLoaderError> {
+        if self.settings.columns.is_empty() {
+            return Ok(None);
+        }
+
+        let mut indices: Vec<usize> = Vec::with_capacity(self.settings.columns.len() + 1);
+
+        if let Ok(index) = schema.index_of(INDEX_COLUMN) {
+            indices.push(index);
+        }
+
+        for column in self.settings.columns.iter() {
+            indices.push(schema.index_of(column).map_err(|_| DataLoaderError::GenericError(format!("Column '{}' was not found in the Arrow IPC file", column)))?);
+        }
+
+        Ok(Some(indices))
+    }
+}
+
+#[async_trait]
+impl DataLoader for ArrowIPCLoader {
+    async fn load_from<'a>(&'a self, path: &'a str) -> Result<DataMatrix, DataLoaderError> {
+        // The IPC readers report the full file schema even when projecting, so the
+        // projected schema is derived here to line up with the returned batches.
+        let schema: SchemaRef = self.read_schema(path)?;
+        let projection: Option<Vec<usize>> = self.projection(&schema)?;
+        let schema: SchemaRef = match projection.as_ref() {
+            Some(indices) => Arc::new(schema.project(indices).map_err(|e| DataLoaderError::GenericError(e.to_string()))?),
+            None => schema
+        };
+
+        let batches: Result<Vec<RecordBatch>, _> = match self.settings.format {
+            ArrowIPCFormat::File => FileReader::try_new(Self::open(path)?, projection)
+                .map_err(|e| DataLoaderError::ParseError(e.to_string()))?
+                .collect(),
+            ArrowIPCFormat::Stream => StreamReader::try_new(Self::open(path)?, projection)
+                .map_err(|e| DataLoaderError::ParseError(e.to_string()))?
+                .collect()
+        };
+
+        let batches: Vec<RecordBatch> = batches.map_err(|e| DataLoaderError::ParseError(e.to_string()))?;
+
+        DataMatrix::from_record_batches(schema, &batches)
+    }
+}
@@ -0,0 +1,185 @@
+use std::io::Read;
+
+use async_trait::async_trait;
+use indexmap::{IndexMap, IndexSet};
+use serde_json::{Map, Value};
+
+use crate::{data_matrix::DataMatrix, data_vector::DataVector, enums::error_types::DataLoaderError};
+
+use super::{data_loader_settings::DataLoaderSettings, DataLoader};
+
+pub struct JSONLoader {
+    pub settings: JSONLoaderSettings
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum JSONFormat {
+    Auto,
+    Records,
+    NDJSON
+}
+
+#[derive(Clone)]
+pub struct JSONLoaderSettings {
+    pub format: JSONFormat,
+    pub skip_rows: usize,
+    pub n_rows: usize
+}
+
+impl Default for JSONLoaderSettings {
+    fn default() -> Self {
+        Self {
+            format: JSONFormat::Auto,
+            skip_rows: 0,
+            n_rows: usize::MAX
+        }
+    }
+}
+
+impl DataLoaderSettings for JSONLoaderSettings {}
+
+impl JSONLoader {
+    pub fn new(settings: JSONLoaderSettings) -> Self {
+        JSONLoader {
+            settings
+        }
+    }
+
+    fn detect_format(&self, contents: &str) -> JSONFormat {
+        match self.settings.format {
+            JSONFormat::Auto => {
+                if contents.trim_start().starts_with('[') {
+                    JSONFormat::Records
+                } else {
+                    JSONFormat::NDJSON
+                }
+            },
+            ref format => format.clone()
+        }
+    }
+
+    fn parse_records(&self, contents: &str) -> Result<Vec<Map<String, Value>>, DataLoaderError> {
+        let value: Value = serde_json::from_str(contents).map_err(|e| DataLoaderError::ParseError(e.to_string()))?;
+
+        match value {
+            Value::Array(records) => records
+                .into_iter()
+                .enumerate()
+                .map(|(i, record)| Self::into_object(record, format!("Record {}", i)))
+                .collect(),
+            _ => Err(DataLoaderError::ParseError("Expected a top-level array of records".to_string()))
+        }
+    }
+
+    fn parse_ndjson(&self, contents: &str) -> Result<Vec<Map<String, Value>>, DataLoaderError> {
+        contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                let record: Value = serde_json::from_str(line)
+                    .map_err(|e| DataLoaderError::ParseError(format!("Line {}: {}", i + 1, e)))?;
+                Self::into_object(record, format!("Line {}", i + 1))
+            })
+            .collect()
+    }
+
+    // location names the record in errors: its position in an array of records, or its
+    // 1-based line number in NDJSON.
+    fn into_object(record: Value, location: String) -> Result<Map<String, Value>, DataLoaderError> {
+        match record {
+            Value::Object(map) => Ok(map),
+            _ => Err(DataLoaderError::ParseError(format!("{} is not a JSON object", location)))
+        }
+    }
+
+    // Missing keys and JSON nulls become nulls in the resulting column.
+    fn build_column(header: String, values: Vec<&Value>) -> DataVector {
+        let present: Vec<&Value> = values.iter().copied().filter(|v| !v.is_null()).collect();
+
+        if !present.is_empty() && present.iter().all(|v| v.is_boolean()) {
+            let parsed: Vec<Option<bool>> = values.iter().map(|v| v.as_bool()).collect();
+            return DataVector::from_nullable(Some(header), parsed);
+        }
+
+        if !present.is_empty() && present.iter().all(|v| v.is_i64()) {
+            let parsed: Vec<Option<i64>> = values.iter().map(|v| v.as_i64()).collect();
+            return DataVector::from_nullable(Some(header), parsed);
+        }
+
+        if !present.is_empty() && present.iter().all(|v| v.is_number()) {
+            let parsed: Vec<Option<f64>> = values.iter().map(|v| v.as_f64()).collect();
+            return DataVector::from_nullable(Some(header), parsed);
+        }
+
+        let parsed: Vec<Option<String>> = values
+            .iter()
+            .map(|v| match v {
+                Value::Null => None,
+                Value::String(s) => Some(s.clone()),
+                other => Some(other.to_string())
+            })
+            .collect();
+
+        DataVector::from_nullable(Some(header), parsed)
+    }
+
+    pub fn load_from_reader<R: Read>(&self, mut reader: R) -> Result<DataMatrix, DataLoaderError> {
+        let mut contents: String = String::new();
+        reader.read_to_string(&mut contents).map_err(|e| DataLoaderError::FileRead(e.to_string()))?;
+
+        let records: Vec<Map<String, Value>> = match self.detect_format(&contents) {
+            JSONFormat::NDJSON => self.parse_ndjson(&contents)?,
+            _ => self.parse_records(&contents)?
+        };
+
+        let records: Vec<Map<String, Value>> = records
+            .into_iter()
+            .skip(self.settings.skip_rows)
+            .take(self.settings.n_rows)
+            .collect();
+
+        // Columns keep the order in which their keys first appear.
+        let headers: IndexSet<String> = records.iter().flat_map(|record| record.keys().cloned()).collect();
+
+        let null: Value = Value::Null;
+        let mut columns: IndexMap<String, DataVector> = IndexMap::new();
+
+        for header in headers {
+            let values: Vec<&Value> = records
+                .iter()
+                .map(|record| record.get(&header).unwrap_or(&null))
+                .collect();
+
+            columns.insert(header.clone(), Self::build_column(header, values));
+        }
+
+        let index: Vec<String> = (0..records.len()).map(|i| format!("{}", i)).collect();
+
+        Ok(DataMatrix { columns, index })
+    }
+}
+
+#[async_trait]
+impl DataLoader for JSONLoader {
+    async fn load_from<'a>(&'a self, path: &'a str) -> Result<DataMatrix, DataLoaderError> {
+        let file: std::fs::File = std::fs::File::open(path).map_err(|e| DataLoaderError::FileRead(e.to_string()))?;
+        self.load_from_reader(file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn columns_keep_key_order() {
+        let loader: JSONLoader = JSONLoader::new(JSONLoaderSettings::default());
+
+        let records: DataMatrix = loader.load_from_reader(r#"[{"zeta": 1, "alpha": 2}, {"mid": 3, "alpha": 4}]"#.as_bytes()).unwrap();
+        assert_eq!(records.column_names(), vec!["zeta", "alpha", "mid"]);
+
+        let lines: DataMatrix = loader.load_from_reader("{\"zeta\": 1, \"alpha\": 2}\n{\"mid\": 3}\n".as_bytes()).unwrap();
+        assert_eq!(lines.column_names(), vec!["zeta", "alpha", "mid"]);
+    }
+}
@@ -2,9 +2,19 @@ use async_trait::async_trait;
 
 use crate::{data_matrix::DataMatrix, enums::error_types::DataLoaderError};
 
+mod column_inference;
+mod csv_parallel;
+mod csv_records;
 pub mod data_loader_settings;
 pub mod data_types;
+pub mod archive_loader;
 pub mod csv_loader;
+pub mod excel_loader;
+pub mod ipc_loader;
+pub mod json_loader;
+pub mod parquet_loader;
+pub mod sql_loader;
+pub mod xml_loader;
 
 
 #[async_trait]
@@ -0,0 +1,217 @@
+use std::{collections::HashMap, fs::File, sync::Arc};
+
+use arrow_array::{ArrayRef, RecordBatch, RecordBatchReader};
+use arrow_schema::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use async_trait::async_trait;
+use parquet::{arrow::{arrow_reader::ParquetRecordBatchReaderBuilder, ArrowWriter, ProjectionMask}, basic::Compression, file::properties::WriterProperties};
+
+use crate::{data_matrix::{DataMatrix, INDEX_COLUMN}, enums::error_types::DataLoaderError};
+
+use super::{data_loader_settings::DataLoaderSettings, DataLoader};
+
+pub struct ParquetLoader {
+    pub settings: ParquetLoaderSettings
+}
+
+#[derive(Clone)]
+pub struct ParquetLoaderSettings {
+    pub columns: Vec<String>,
+    pub row_groups: Vec<usize>,
+    pub n_rows: usize,
+    pub write_index: bool
+}
+
+// Parquet has no duration type, so durations are stored as Int64 microseconds tagged with this field metadata key
+const DURATION_METADATA: &str = "veracity:duration";
+
+impl Default for ParquetLoaderSettings {
+    fn default() -> Self {
+        Self {
+            columns: Vec::new(),
+            row_groups: Vec::new(),
+            n_rows: usize::MAX,
+            write_index: true
+        }
+    }
+}
+
+impl DataLoaderSettings for ParquetLoaderSettings {}
+
+impl ParquetLoader {
+    pub fn new(settings: ParquetLoaderSettings) -> Self {
+        ParquetLoader {
+            settings
+        }
+    }
+
+    pub async fn write_to(&self, matrix: &DataMatrix, path: &str) -> Result<(), DataLoaderError> {
+        let batch: RecordBatch = Self::encode_durations(matrix.to_record_batch(self.settings.write_index)?)?;
+
+        let file: File = File::create(path).map_err(|e| DataLoaderError::FileWrite(e.to_string()))?;
+        let properties: WriterProperties = WriterProperties::builder().set_compression(Compression::SNAPPY).build();
+        let mut writer: ArrowWriter<File> = ArrowWriter::try_new(file, batch.schema(), Some(properties))
+            .map_err(|e| DataLoaderError::GenericError(e.to_string()))?;
+
+        writer.write(&batch).map_err(|e| DataLoaderError::FileWrite(e.to_string()))?;
+        writer.close().map_err(|e| DataLoaderError::FileWrite(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn encode_durations(batch: RecordBatch) -> Result<RecordBatch, DataLoaderError> {
+        Self::map_fields(batch, |field| match field.data_type() {
+            DataType::Duration(TimeUnit::Microsecond) => Some(
+                Field::new(field.name(), DataType::Int64, field.is_nullable()).with_metadata(HashMap::from([(DURATION_METADATA.to_string(), "us".to_string())]))
+            ),
+            _ => None
+        })
+    }
+
+    fn decode_durations(batch: RecordBatch) -> Result<RecordBatch, DataLoaderError> {
+        Self::map_fields(batch, |field| match field.metadata().get(DURATION_METADATA) {
+            Some(_) if field.data_type() == &DataType::Int64 => Some(Field::new(field.name(), DataType::Duration(TimeUnit::Microsecond), field.is_nullable())),
+            _ => None
+        })
+    }
+
+    fn map_fields(batch: RecordBatch, convert: impl Fn(&Field) -> Option<Field>) -> Result<RecordBatch, DataLoaderError> {
+        let schema: SchemaRef = batch.schema();
+        if !schema.fields().iter().any(|field| convert(field).is_some()) {
+            return Ok(batch);
+        }
+
+        let mut fields: Vec<Field> = Vec::with_capacity(schema.fields().len());
+        let mut arrays: Vec<ArrayRef> = Vec::with_capacity(schema.fields().len());
+
+        for (field, array) in schema.fields().iter().zip(batch.columns()) {
+            match convert(field) {
+                Some(converted) => {
+                    arrays.push(arrow_cast::cast(array, converted.data_type()).map_err(|e| DataLoaderError::CastError(e.to_string()))?);
+                    fields.push(converted);
+                },
+                None => {
+                    arrays.push(array.clone());
+                    fields.push(field.as_ref().clone());
+                }
+            }
+        }
+
+        RecordBatch::try_new(Arc::new(Schema::new_with_metadata(fields, schema.metadata().clone())), arrays).map_err(|e| DataLoaderError::GenericError(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl DataLoader for ParquetLoader {
+    async fn load_from<'a>(&'a self, path: &'a str) -> Result<DataMatrix, DataLoaderError> {
+        let file: File = File::open(path).map_err(|e| DataLoaderError::FileRead(e.to_string()))?;
+        let mut builder = ParquetRecordBatchReaderBuilder::try_new(file).map_err(|e| DataLoaderError::ParseError(e.to_string()))?;
+
+        if !self.settings.columns.is_empty() {
+            let schema = builder.schema().clone();
+            let mut indices: Vec<usize> = Vec::with_capacity(self.settings.columns.len() + 1);
+
+            if let Ok(index) = schema.index_of(INDEX_COLUMN) {
+                indices.push(index);
+            }
+
+            for column in self.settings.columns.iter() {
+                indices.push(schema.index_of(column).map_err(|_| DataLoaderError::GenericError(format!("Column '{}' was not found in the Parquet file", column)))?);
+            }
+
+            let mask: ProjectionMask = ProjectionMask::roots(builder.parquet_schema(), indices);
+            builder = builder.with_projection(mask);
+        }
+
+        if !self.settings.row_groups.is_empty() {
+            let num_row_groups: usize = builder.metadata().num_row_groups();
+            if let Some(row_group) = self.settings.row_groups.iter().find(|&&row_group| row_group >= num_row_groups) {
+                return Err(DataLoaderError::GenericError(format!("Row group {} is out of range for a file with {} row groups", row_group, num_row_groups)));
+            }
+            builder = builder.with_row_groups(self.settings.row_groups.clone());
+        }
+
+        if self.settings.n_rows != usize::MAX {
+            builder = builder.with_limit(self.settings.n_rows);
+        }
+
+        let reader = builder.build().map_err(|e| DataLoaderError::ParseError(e.to_string()))?;
+        let schema: SchemaRef = Self::decode_durations(RecordBatch::new_empty(reader.schema()))?.schema();
+        let batches: Vec<RecordBatch> = reader
+            .map(|batch| Self::decode_durations(batch.map_err(|e| DataLoaderError::ParseError(e.to_string()))?))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        DataMatrix::from_record_batches(schema, &batches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use veracity_types::dtype::DType;
+
+    use crate::data_vector::DataVector;
+
+    use super::*;
+
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir().join(format!("veracity_parquet_{}_{}.parquet", std::process::id(), name)).to_str().unwrap().to_string()
+    }
+
+    fn matrix() -> DataMatrix {
+        let label = |name: &str| Some(name.to_string());
+        let mut matrix: DataMatrix = DataMatrix::from_vec(vec![
+            DataVector::from_nullable(label("bool"), vec![Some(true), None, Some(false)]),
+            DataVector::from_nullable(label("i32"), vec![Some(1i32), Some(-2), None]),
+            DataVector::from_nullable(label("i64"), vec![None, Some(i64::MAX), Some(3)]),
+            DataVector::from_nullable(label("f32"), vec![Some(1.5f32), None, Some(-0.25)]),
+            DataVector::from_nullable(label("f64"), vec![Some(0.1), Some(f64::MAX), None]),
+            DataVector::from_nullable(label("utf8"), vec![Some("a".to_string()), None, Some(String::new())]),
+            DataVector::from_categorical(label("categorical"), vec![Some("low".to_string()), Some("high".to_string()), None]),
+            DataVector::from_micros(label("datetime"), vec![Some(1_704_196_800_000_000), None, Some(-1)], DType::Datetime),
+            DataVector::from_micros(label("duration"), vec![None, Some(90_000_000), Some(-5)], DType::Duration)
+        ])
+        .unwrap();
+        matrix.set_index(vec!["x", "y", "z"]).unwrap();
+        matrix
+    }
+
+    async fn write_and_load(name: &str, settings: ParquetLoaderSettings) -> Result<DataMatrix, DataLoaderError> {
+        let path: String = temp_path(name);
+        ParquetLoader::new(ParquetLoaderSettings::default()).write_to(&matrix(), &path).await.unwrap();
+
+        let loaded: Result<DataMatrix, DataLoaderError> = ParquetLoader::new(settings).load_from(&path).await;
+        std::fs::remove_file(&path).unwrap();
+        loaded
+    }
+
+    #[tokio::test]
+    async fn round_trip_keeps_every_dtype_and_the_index() {
+        let original: DataMatrix = matrix();
+        let loaded: DataMatrix = write_and_load("round_trip", ParquetLoaderSettings::default()).await.unwrap();
+
+        assert_eq!(loaded.index, original.index);
+        assert_eq!(loaded.column_names(), original.column_names());
+        for (column, expected) in loaded.columns.values().zip(original.columns.values()) {
+            assert_eq!(column.dtype(), expected.dtype(), "{:?}", expected.label);
+            assert_eq!(column.data, expected.data, "{:?}", expected.label);
+            assert_eq!(column.validity, expected.validity, "{:?}", expected.label);
+        }
+    }
+
+    #[tokio::test]
+    async fn projection_keeps_the_index() {
+        let settings: ParquetLoaderSettings = ParquetLoaderSettings { columns: vec!["f64".to_string(), "bool".to_string()], ..Default::default() };
+        let loaded: DataMatrix = write_and_load("projection", settings).await.unwrap();
+
+        assert_eq!(loaded.index, vec!["x", "y", "z"]);
+        assert_eq!(loaded.column_names(), vec!["bool", "f64"]);
+    }
+
+    #[tokio::test]
+    async fn row_groups_are_checked() {
+        let first: ParquetLoaderSettings = ParquetLoaderSettings { row_groups: vec![0], ..Default::default() };
+        assert_eq!(write_and_load("row_group_0", first).await.unwrap().num_rows(), 3);
+
+        let missing: ParquetLoaderSettings = ParquetLoaderSettings { row_groups: vec![1], ..Default::default() };
+        assert!(matches!(write_and_load("row_group_1", missing).await, Err(DataLoaderError::GenericError(_))));
+    }
+}
@@ -0,0 +1,346 @@
+use async_trait::async_trait;
+use indexmap::IndexMap;
+use rusqlite::{params_from_iter, types::Value, Connection};
+use veracity_types::dtype::DType;
+
+use crate::{column_data::format_datetime, data_matrix::DataMatrix, data_vector::DataVector, enums::error_types::DataLoaderError, temporal::{default_date_formats, parse_datetime}};
+
+use super::{data_loader_settings::DataLoaderSettings, DataLoader};
+
+pub struct SQLLoader {
+    pub settings: SQLLoaderSettings
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SQLIfExists {
+    Fail,
+    Replace,
+    Append
+}
+
+pub struct SQLLoaderSettings {
+    pub query: String,
+    pub if_exists: SQLIfExists
+}
+
+impl Default for SQLLoaderSettings {
+    fn default() -> Self {
+        Self {
+            query: String::new(),
+            if_exists: SQLIfExists::Fail
+        }
+    }
+}
+
+impl DataLoaderSettings for SQLLoaderSettings {}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SQLAffinity {
+    Bool,
+    Integer,
+    Real,
+    Text,
+    Datetime,
+    Unknown
+}
+
+impl SQLLoader {
+    pub fn new(settings: SQLLoaderSettings) -> Self {
+        SQLLoader {
+            settings
+        }
+    }
+
+    pub async fn write_to(&self, matrix: &DataMatrix, path: &str, table: &str) -> Result<(), DataLoaderError> {
+        let mut connection: Connection = Self::open(path)?;
+        let transaction = connection.transaction().map_err(|e| DataLoaderError::GenericError(e.to_string()))?;
+
+        let table_name: String = Self::quote_identifier(table);
+
+        let table_exists: bool = transaction
+            .query_row("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1", [table], |row| row.get::<_, i64>(0))
+            .map_err(|e| DataLoaderError::GenericError(e.to_string()))? > 0;
+
+        if table_exists {
+            match self.settings.if_exists {
+                SQLIfExists::Fail => return Err(DataLoaderError::GenericError(format!("Table '{}' already exists", table))),
+                SQLIfExists::Replace => {
+                    transaction
+                        .execute(&format!("DROP TABLE {}", table_name), [])
+                        .map_err(|e| DataLoaderError::GenericError(e.to_string()))?;
+                },
+                SQLIfExists::Append => {}
+            }
+        }
+
+        let mut column_names: Vec<String> = Vec::with_capacity(matrix.columns.len());
+        let mut column_values: Vec<Vec<Value>> = Vec::with_capacity(matrix.columns.len());
+        let mut column_definitions: Vec<String> = Vec::with_capacity(matrix.columns.len());
+
+        for (label, column) in matrix.columns.iter() {
+            let (sql_type, values) = Self::column_to_values(column)?;
+            column_definitions.push(format!("{} {}", Self::quote_identifier(label), sql_type));
+            column_names.push(Self::quote_identifier(label));
+            column_values.push(values);
+        }
+
+        if !table_exists || self.settings.if_exists == SQLIfExists::Replace {
+            transaction
+                .execute(&format!("CREATE TABLE {} ({})", table_name, column_definitions.join(", ")), [])
+                .map_err(|e| DataLoaderError::GenericError(e.to_string()))?;
+        }
+
+        let placeholders: Vec<String> = (1..=column_names.len()).map(|i| format!("?{}", i)).collect();
+        let insert: String = format!("INSERT INTO {} ({}) VALUES ({})", table_name, column_names.join(", "), placeholders.join(", "));
+
+        {
+            let mut statement = transaction.prepare(&insert).map_err(|e| DataLoaderError::GenericError(e.to_string()))?;
+            let num_rows: usize = matrix.columns.values().next().map(|col| col.len).unwrap_or(0);
+
+            for row_idx in 0..num_rows {
+                statement
+                    .execute(params_from_iter(column_values.iter().map(|values| &values[row_idx])))
+                    .map_err(|e| DataLoaderError::GenericError(e.to_string()))?;
+            }
+        }
+
+        transaction.commit().map_err(|e| DataLoaderError::GenericError(e.to_string()))
+    }
+
+    fn open(path: &str) -> Result<Connection, DataLoaderError> {
+        let path: &str = path.strip_prefix("sqlite://").unwrap_or(path);
+        Connection::open(path).map_err(|e| DataLoaderError::FileRead(e.to_string()))
+    }
+
+    fn quote_identifier(identifier: &str) -> String {
+        format!("\"{}\"", identifier.replace('"', "\"\""))
+    }
+
+    fn column_to_values(column: &DataVector) -> Result<(&'static str, Vec<Value>), DataLoaderError> {
+        match column.dtype() {
+            DType::Bool => Ok(("BOOLEAN", column.to_options::<bool>()?.into_iter().map(|v| v.map_or(Value::Null, |v| Value::Integer(v as i64))).collect())),
+            DType::I32 => Ok(("INTEGER", column.to_options::<i32>()?.into_iter().map(|v| v.map_or(Value::Null, |v| Value::Integer(v as i64))).collect())),
+            DType::I64 => Ok(("INTEGER", column.to_options::<i64>()?.into_iter().map(|v| v.map_or(Value::Null, Value::Integer)).collect())),
+            DType::F32 => Ok(("REAL", column.to_options::<f32>()?.into_iter().map(|v| v.filter(|v| !v.is_nan()).map_or(Value::Null, |v| Value::Real(v as f64))).collect())),
+            DType::F64 => Ok(("REAL", column.to_options::<f64>()?.into_iter().map(|v| v.filter(|v| !v.is_nan()).map_or(Value::Null, Value::Real)).collect())),
+            DType::Utf8 | DType::Categorical => Ok(("TEXT", column.to_options::<String>()?.into_iter().map(|v| v.map_or(Value::Null, Value::Text)).collect())),
+            DType::Datetime => Ok(("DATETIME", column.to_options::<i64>()?.into_iter().map(|v| v.map_or(Value::Null, |v| Value::Text(format_datetime(v)))).collect())),
+            DType::Duration => Ok(("INTEGER", column.to_options::<i64>()?.into_iter().map(|v| v.map_or(Value::Null, Value::Integer)).collect()))
+        }
+    }
+
+    // Follows SQLite's type affinity rules for declared column types.
+    fn affinity(decl_type: Option<&str>) -> SQLAffinity {
+        let decl_type: String = match decl_type {
+            Some(decl_type) => decl_type.to_uppercase(),
+            None => return SQLAffinity::Unknown
+        };
+
+        // DATETIME, DATE and TIMESTAMP are not SQLite affinities, but write_to declares
+        // datetime columns as DATETIME and stores them as text.
+        if decl_type.contains("DATE") || decl_type.contains("TIMESTAMP") {
+            SQLAffinity::Datetime
+        } else if decl_type.contains("BOOL") {
+            SQLAffinity::Bool
+        } else if decl_type.contains("INT") {
+            SQLAffinity::Integer
+        } else if decl_type.contains("CHAR") || decl_type.contains("CLOB") || decl_type.contains("TEXT") {
+            SQLAffinity::Text
+        } else if decl_type.contains("REAL") || decl_type.contains("FLOA") || decl_type.contains("DOUB") {
+            SQLAffinity::Real
+        } else {
+            SQLAffinity::Unknown
+        }
+    }
+
+    fn infer_affinity(values: &[Value]) -> SQLAffinity {
+        let present: Vec<&Value> = values.iter().filter(|v| !matches!(v, Value::Null)).collect();
+
+        if present.is_empty() {
+            SQLAffinity::Text
+        } else if present.iter().all(|v| matches!(v, Value::Integer(_))) {
+            SQLAffinity::Integer
+        } else if present.iter().all(|v| matches!(v, Value::Integer(_) | Value::Real(_))) {
+            SQLAffinity::Real
+        } else {
+            SQLAffinity::Text
+        }
+    }
+
+    // NULLs become nulls in the resulting column regardless of its affinity. Datetime
+    // columns holding anything other than parseable text are read by their values instead.
+    fn build_column(header: String, affinity: SQLAffinity, values: Vec<Value>) -> DataVector {
+        let all_integers: bool = values.iter().all(|v| matches!(v, Value::Integer(_) | Value::Null));
+        let all_numeric: bool = values.iter().all(|v| matches!(v, Value::Integer(_) | Value::Real(_) | Value::Null));
+
+        if affinity == SQLAffinity::Datetime {
+            let date_formats: Vec<String> = default_date_formats();
+            let parsed: Option<Vec<Option<i64>>> = values
+                .iter()
+                .map(|v| match v {
+                    Value::Null => Some(None),
+                    Value::Text(s) => parse_datetime(s, &date_formats).map(Some),
+                    _ => None
+                })
+                .collect();
+
+            if let Some(parsed) = parsed {
+                return DataVector::from_micros(Some(header), parsed, DType::Datetime);
+            }
+        }
+
+        let affinity: SQLAffinity = match affinity {
+            SQLAffinity::Unknown | SQLAffinity::Datetime => Self::infer_affinity(&values),
+            SQLAffinity::Bool if !all_integers => Self::infer_affinity(&values),
+            SQLAffinity::Integer if !all_integers => Self::infer_affinity(&values),
+            SQLAffinity::Real if !all_numeric => SQLAffinity::Text,
+            affinity => affinity
+        };
+
+        match affinity {
+            SQLAffinity::Bool => {
+                let parsed: Vec<Option<bool>> = values.iter().map(|v| if let Value::Integer(i) = v { Some(*i != 0) } else { None }).collect();
+                DataVector::from_nullable(Some(header), parsed)
+            },
+            SQLAffinity::Integer => {
+                let parsed: Vec<Option<i64>> = values.iter().map(|v| if let Value::Integer(i) = v { Some(*i) } else { None }).collect();
+                DataVector::from_nullable(Some(header), parsed)
+            },
+            SQLAffinity::Real => {
+                let parsed: Vec<Option<f64>> = values
+                    .iter()
+                    .map(|v| match v {
+                        Value::Integer(i) => Some(*i as f64),
+                        Value::Real(r) => Some(*r),
+                        _ => None
+                    })
+                    .collect();
+                DataVector::from_nullable(Some(header), parsed)
+            },
+            SQLAffinity::Text | SQLAffinity::Datetime | SQLAffinity::Unknown => {
+                let parsed: Vec<Option<String>> = values
+                    .into_iter()
+                    .map(|v| match v {
+                        Value::Null => None,
+                        Value::Integer(i) => Some(i.to_string()),
+                        Value::Real(r) => Some(r.to_string()),
+                        Value::Text(s) => Some(s),
+                        Value::Blob(b) => Some(String::from_utf8_lossy(&b).into_owned())
+                    })
+                    .collect();
+                DataVector::from_nullable(Some(header), parsed)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl DataLoader for SQLLoader {
+    async fn load_from<'a>(&'a self, path: &'a str) -> Result<DataMatrix, DataLoaderError> {
+        let connection: Connection = Self::open(path)?;
+        let mut statement = connection.prepare(&self.settings.query).map_err(|e| DataLoaderError::ParseError(e.to_string()))?;
+
+        let headers: Vec<(String, SQLAffinity)> = statement
+            .columns()
+            .iter()
+            .map(|column| (column.name().to_string(), Self::affinity(column.decl_type())))
+            .collect();
+
+        let mut raw_columns: Vec<Vec<Value>> = vec![Vec::new(); headers.len()];
+        let mut rows = statement.query([]).map_err(|e| DataLoaderError::GenericError(e.to_string()))?;
+
+        while let Some(row) = rows.next().map_err(|e| DataLoaderError::GenericError(e.to_string()))? {
+            for (col_idx, values) in raw_columns.iter_mut().enumerate() {
+                let value: Value = row.get(col_idx).map_err(|e| DataLoaderError::GenericError(e.to_string()))?;
+                values.push(value);
+            }
+        }
+
+        let row_count: usize = raw_columns.first().map(|values| values.len()).unwrap_or(0);
+        let mut columns: IndexMap<String, DataVector> = IndexMap::new();
+
+        for ((header, affinity), values) in headers.into_iter().zip(raw_columns) {
+            if columns.contains_key(&header) {
+                return Err(DataLoaderError::DuplicateLabel);
+            }
+            columns.insert(header.clone(), Self::build_column(header, affinity, values));
+        }
+
+        let index: Vec<String> = (0..row_count).map(|i| format!("{}", i)).collect();
+
+        Ok(DataMatrix { columns, index })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn datetime_declared_columns_are_parsed() {
+        assert_eq!(SQLLoader::affinity(Some("DATETIME")), SQLAffinity::Datetime);
+        assert_eq!(SQLLoader::affinity(Some("timestamp")), SQLAffinity::Datetime);
+
+        let values: Vec<Value> = vec![Value::Text("2024-01-02 12:00:00".to_string()), Value::Null, Value::Text("2024-01-03 08:30:00.250".to_string())];
+        let column: DataVector = SQLLoader::build_column("when".to_string(), SQLAffinity::Datetime, values);
+
+        assert_eq!(column.dtype(), DType::Datetime);
+        assert_eq!(column.to_options::<i64>().unwrap(), vec![Some(1_704_196_800_000_000), None, Some(1_704_270_600_250_000)]);
+    }
+
+    #[test]
+    fn unparseable_datetime_columns_fall_back_to_their_values() {
+        let values: Vec<Value> = vec![Value::Text("2024-01-02".to_string()), Value::Text("soon".to_string())];
+        let column: DataVector = SQLLoader::build_column("when".to_string(), SQLAffinity::Datetime, values);
+
+        assert_eq!(column.dtype(), DType::Utf8);
+    }
+
+    fn matrix(ids: Vec<i64>) -> DataMatrix {
+        let rows: usize = ids.len();
+        DataMatrix::from_vec(vec![
+            DataVector::from_nullable(Some("id".to_string()), ids.into_iter().map(Some).collect()),
+            DataVector::from_nullable(Some("name".to_string()), (0..rows).map(|row| (row % 2 == 0).then(|| format!("n{}", row))).collect()),
+            DataVector::from_nullable(Some("score".to_string()), (0..rows).map(|row| Some(row as f64 + 0.5)).collect()),
+            DataVector::from_nullable(Some("flag".to_string()), (0..rows).map(|row| Some(row % 2 == 1)).collect()),
+            DataVector::from_micros(Some("when".to_string()), (0..rows).map(|row| Some(1_704_196_800_000_000 + row as i64 * 1_000_000)).collect(), DType::Datetime)
+        ])
+        .unwrap()
+    }
+
+    async fn write(path: &str, matrix: &DataMatrix, if_exists: SQLIfExists) -> Result<(), DataLoaderError> {
+        SQLLoader::new(SQLLoaderSettings { if_exists, ..Default::default() }).write_to(matrix, path, "items").await
+    }
+
+    async fn load(path: &str) -> DataMatrix {
+        SQLLoader::new(SQLLoaderSettings { query: "SELECT * FROM items ORDER BY id".to_string(), ..Default::default() }).load_from(path).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn write_then_load_round_trips_and_honours_if_exists() {
+        let path: String = std::env::temp_dir().join(format!("veracity_sql_{}_if_exists.sqlite", std::process::id())).to_str().unwrap().to_string();
+        let _ = std::fs::remove_file(&path);
+
+        write(&path, &matrix(vec![1, 2]), SQLIfExists::Fail).await.unwrap();
+        let loaded: DataMatrix = load(&path).await;
+        let expected: DataMatrix = matrix(vec![1, 2]);
+
+        assert_eq!(loaded.column_names(), expected.column_names());
+        for (column, expected) in loaded.columns.values().zip(expected.columns.values()) {
+            assert_eq!(column.dtype(), expected.dtype(), "{:?}", expected.label);
+            assert_eq!(column.data, expected.data, "{:?}", expected.label);
+            assert_eq!(column.validity, expected.validity, "{:?}", expected.label);
+        }
+
+        assert!(matches!(write(&path, &matrix(vec![3]), SQLIfExists::Fail).await, Err(DataLoaderError::GenericError(_))));
+        assert_eq!(load(&path).await.num_rows(), 2);
+
+        write(&path, &matrix(vec![3]), SQLIfExists::Append).await.unwrap();
+        assert_eq!(load(&path).await.get_column("id").unwrap().to_options::<i64>().unwrap(), vec![Some(1), Some(2), Some(3)]);
+
+        write(&path, &matrix(vec![7]), SQLIfExists::Replace).await.unwrap();
+        assert_eq!(load(&path).await.get_column("id").unwrap().to_options::<i64>().unwrap(), vec![Some(7)]);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
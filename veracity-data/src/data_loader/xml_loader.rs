@@ -0,0 +1,277 @@
+use std::{collections::HashMap, io::{BufReader, Read}};
+
+use async_trait::async_trait;
+use indexmap::IndexMap;
+use quick_xml::{escape::unescape, events::{BytesStart, Event}, Reader};
+
+use crate::{data_matrix::DataMatrix, enums::error_types::DataLoaderError};
+
+use super::{column_inference::infer_column, data_loader_settings::DataLoaderSettings, DataLoader};
+
+pub struct XMLLoader {
+    pub settings: XMLLoaderSettings
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum XMLFieldSource {
+    Attributes,
+    Elements,
+    Both
+}
+
+#[derive(Clone)]
+pub struct XMLLoaderSettings {
+    pub record_path: String,
+    pub field_source: XMLFieldSource,
+    pub attribute_prefix: String,
+    pub skip_rows: usize,
+    pub n_rows: usize
+}
+
+impl Default for XMLLoaderSettings {
+    fn default() -> Self {
+        Self {
+            record_path: "/*/*".to_string(),
+            field_source: XMLFieldSource::Both,
+            attribute_prefix: String::new(),
+            skip_rows: 0,
+            n_rows: usize::MAX
+        }
+    }
+}
+
+impl DataLoaderSettings for XMLLoaderSettings {}
+
+struct RecordPath {
+    absolute: bool,
+    segments: Vec<String>
+}
+
+impl RecordPath {
+    // Supports "/a/b" (from the document root), "//b" or "a/b" (at any depth) and "*" wildcards.
+    fn parse(path: &str) -> Result<Self, DataLoaderError> {
+        let absolute: bool = path.starts_with('/') && !path.starts_with("//");
+        let segments: Vec<String> = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| segment.to_string())
+            .collect();
+
+        if segments.is_empty() {
+            return Err(DataLoaderError::GenericError(format!("'{}' is not a valid record_path", path)));
+        }
+
+        Ok(RecordPath { absolute, segments })
+    }
+
+    fn matches(&self, stack: &[String]) -> bool {
+        if stack.len() < self.segments.len() || (self.absolute && stack.len() != self.segments.len()) {
+            return false;
+        }
+
+        stack[stack.len() - self.segments.len()..]
+            .iter()
+            .zip(self.segments.iter())
+            .all(|(name, segment)| segment == "*" || name == segment)
+    }
+}
+
+struct RecordBuilder {
+    depth: usize,
+    fields: HashMap<String, String>,
+    text: String
+}
+
+impl XMLLoader {
+    pub fn new(settings: XMLLoaderSettings) -> Self {
+        XMLLoader {
+            settings
+        }
+    }
+
+    fn read_attributes(&self, element: &BytesStart, fields: &mut HashMap<String, String>, headers: &mut Vec<String>) -> Result<(), DataLoaderError> {
+        if self.settings.field_source == XMLFieldSource::Elements {
+            return Ok(());
+        }
+
+        for attribute in element.attributes() {
+            let attribute = attribute.map_err(|e| DataLoaderError::ParseError(e.to_string()))?;
+            let key: String = format!("{}{}", self.settings.attribute_prefix, String::from_utf8_lossy(attribute.key.local_name().as_ref()));
+            let value: String = attribute.unescape_value().map_err(|e| DataLoaderError::ParseError(e.to_string()))?.into_owned();
+            Self::insert_field(fields, headers, key, value);
+        }
+
+        Ok(())
+    }
+
+    fn insert_field(fields: &mut HashMap<String, String>, headers: &mut Vec<String>, key: String, value: String) {
+        if !headers.contains(&key) {
+            headers.push(key.clone());
+        }
+        fields.insert(key, value);
+    }
+
+    pub fn load_from_reader<R: Read>(&self, reader: R) -> Result<DataMatrix, DataLoaderError> {
+        let record_path: RecordPath = RecordPath::parse(&self.settings.record_path)?;
+        let read_elements: bool = self.settings.field_source != XMLFieldSource::Attributes;
+
+        let mut reader: Reader<BufReader<R>> = Reader::from_reader(BufReader::new(reader));
+        let mut buf: Vec<u8> = Vec::new();
+
+        let mut stack: Vec<String> = Vec::new();
+        let mut current: Option<RecordBuilder> = None;
+        let mut headers: Vec<String> = Vec::new();
+        let mut records: Vec<HashMap<String, String>> = Vec::new();
+
+        loop {
+            let event: Event = reader
+                .read_event_into(&mut buf)
+                .map_err(|e| DataLoaderError::ParseError(format!("position {}: {}", reader.buffer_position(), e)))?;
+
+            match event {
+                Event::Start(element) => {
+                    stack.push(String::from_utf8_lossy(element.local_name().as_ref()).into_owned());
+
+                    match current.as_mut() {
+                        Some(record) => record.text.clear(),
+                        None if record_path.matches(&stack) => {
+                            let mut fields: HashMap<String, String> = HashMap::new();
+                            self.read_attributes(&element, &mut fields, &mut headers)?;
+                            current = Some(RecordBuilder { depth: stack.len(), fields, text: String::new() });
+                        },
+                        None => {}
+                    }
+                },
+                Event::Empty(element) => {
+                    stack.push(String::from_utf8_lossy(element.local_name().as_ref()).into_owned());
+
+                    if current.is_none() && record_path.matches(&stack) {
+                        let mut fields: HashMap<String, String> = HashMap::new();
+                        self.read_attributes(&element, &mut fields, &mut headers)?;
+                        records.push(fields);
+                    }
+
+                    stack.pop();
+                },
+                Event::Text(text) => {
+                    if let Some(record) = current.as_mut() {
+                        record.text.push_str(&text.xml10_content().map_err(|e| DataLoaderError::ParseError(e.to_string()))?);
+                    }
+                },
+                Event::CData(data) => {
+                    if let Some(record) = current.as_mut() {
+                        record.text.push_str(&data.decode().map_err(|e| DataLoaderError::ParseError(e.to_string()))?);
+                    }
+                },
+                Event::GeneralRef(reference) => {
+                    if let Some(record) = current.as_mut() {
+                        if let Some(c) = reference.resolve_char_ref().map_err(|e| DataLoaderError::ParseError(e.to_string()))? {
+                            record.text.push(c);
+                        } else {
+                            let name = reference.decode().map_err(|e| DataLoaderError::ParseError(e.to_string()))?;
+                            let entity: String = format!("&{};", name);
+                            record.text.push_str(&unescape(&entity).map_err(|e| DataLoaderError::ParseError(e.to_string()))?);
+                        }
+                    }
+                },
+                Event::End(_) => {
+                    if let Some(record) = current.as_mut() {
+                        if stack.len() == record.depth {
+                            records.push(std::mem::take(&mut record.fields));
+                            current = None;
+                        } else if read_elements {
+                            let key: String = stack[record.depth..].join("/");
+                            let value: String = record.text.trim().to_string();
+                            record.text.clear();
+                            if !value.is_empty() {
+                                Self::insert_field(&mut record.fields, &mut headers, key, value);
+                            }
+                        }
+                    }
+                    stack.pop();
+                },
+                Event::Eof => break,
+                _ => {}
+            }
+
+            buf.clear();
+        }
+
+        let records: Vec<HashMap<String, String>> = records
+            .into_iter()
+            .skip(self.settings.skip_rows)
+            .take(self.settings.n_rows)
+            .collect();
+
+        let mut columns = IndexMap::new();
+
+        for header in headers {
+            let values: Vec<Option<String>> = records.iter().map(|record| record.get(&header).cloned()).collect();
+            columns.insert(header.clone(), infer_column(header, values, None));
+        }
+
+        let index: Vec<String> = (0..records.len()).map(|i| format!("{}", i)).collect();
+
+        Ok(DataMatrix { columns, index })
+    }
+}
+
+#[async_trait]
+impl DataLoader for XMLLoader {
+    async fn load_from<'a>(&'a self, path: &'a str) -> Result<DataMatrix, DataLoaderError> {
+        let file: std::fs::File = std::fs::File::open(path).map_err(|e| DataLoaderError::FileRead(e.to_string()))?;
+        self.load_from_reader(file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CATALOG: &str = r#"<?xml version="1.0"?>
+<catalog>
+    <meta><item id="99"><name>ignored</name></item></meta>
+    <books>
+        <item id="1" lang="en"><name>Dune &amp; more</name><price>9.5</price><author><first>Frank</first></author></item>
+        <item id="2"><name><![CDATA[<Emma>]]></name><price>7</price></item>
+        <item id="3" lang="fr"/>
+    </books>
+</catalog>"#;
+
+    fn load(record_path: &str, field_source: XMLFieldSource) -> DataMatrix {
+        let settings: XMLLoaderSettings = XMLLoaderSettings { record_path: record_path.to_string(), field_source, attribute_prefix: "@".to_string(), ..Default::default() };
+        XMLLoader::new(settings).load_from_reader(CATALOG.as_bytes()).unwrap()
+    }
+
+    fn strings(values: &[Option<&str>]) -> Vec<Option<String>> {
+        values.iter().map(|value| value.map(str::to_string)).collect()
+    }
+
+    #[test]
+    fn records_read_attributes_and_nested_elements() {
+        let matrix: DataMatrix = load("/catalog/books/item", XMLFieldSource::Both);
+
+        assert_eq!(matrix.column_names(), vec!["@id", "@lang", "name", "price", "author/first"]);
+        assert_eq!(matrix.get_column("@id").unwrap().to_options::<i64>().unwrap(), vec![Some(1), Some(2), Some(3)]);
+        assert_eq!(matrix.get_column("@lang").unwrap().to_options::<String>().unwrap(), strings(&[Some("en"), None, Some("fr")]));
+        assert_eq!(matrix.get_column("name").unwrap().to_options::<String>().unwrap(), strings(&[Some("Dune & more"), Some("<Emma>"), None]));
+        assert_eq!(matrix.get_column("price").unwrap().to_options::<f64>().unwrap(), vec![Some(9.5), Some(7.0), None]);
+        assert_eq!(matrix.get_column("author/first").unwrap().to_options::<String>().unwrap(), strings(&[Some("Frank"), None, None]));
+    }
+
+    #[test]
+    fn field_source_limits_the_columns() {
+        assert_eq!(load("/catalog/books/item", XMLFieldSource::Attributes).column_names(), vec!["@id", "@lang"]);
+        assert_eq!(load("/catalog/books/item", XMLFieldSource::Elements).column_names(), vec!["name", "price", "author/first"]);
+    }
+
+    #[test]
+    fn relative_paths_match_at_any_depth() {
+        let matrix: DataMatrix = load("//item", XMLFieldSource::Attributes);
+        assert_eq!(matrix.get_column("@id").unwrap().to_options::<i64>().unwrap(), vec![Some(99), Some(1), Some(2), Some(3)]);
+
+        let settings: XMLLoaderSettings = XMLLoaderSettings { record_path: "books/*".to_string(), skip_rows: 1, n_rows: 1, ..Default::default() };
+        let matrix: DataMatrix = XMLLoader::new(settings).load_from_reader(CATALOG.as_bytes()).unwrap();
+        assert_eq!(matrix.get_column("id").unwrap().to_options::<i64>().unwrap(), vec![Some(2)]);
+    }
+}
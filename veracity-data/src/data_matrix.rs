@@ -1,29 +1,36 @@
-use std::{collections::BTreeMap, fmt};
+use std::{cmp::Ordering, collections::{HashMap, HashSet}, fmt, ops::{Bound, RangeBounds}, sync::Arc};
 
+use arrow_array::{ArrayRef, RecordBatch, StringArray};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use indexmap::IndexMap;
 use ndarray::Array2;
+use veracity_types::dtype::DType;
 
-use crate::{data_vector::DataVector, enums::error_types::DataLoaderError};
+use crate::{column_data::ColumnType, data_vector::DataVector, enums::{error_types::DataLoaderError, null_placement::NullPlacement, sample_size::SampleSize}, group_by::GroupBy, random::Rng};
 
+pub const INDEX_COLUMN: &str = "__index__";
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "crate::serde_validation::RawDataMatrix"))]
 pub struct DataMatrix {
-    pub columns: BTreeMap<String, DataVector>,
+    pub columns: IndexMap<String, DataVector>,
     pub index: Vec<String>
 }
 
 impl DataMatrix {
     pub fn new() -> Self {
         DataMatrix { 
-            columns: BTreeMap::new(), 
+            columns: IndexMap::new(), 
             index: Vec::new() 
         }
     }
 
-    pub fn add_column<T: Clone + Send + Sync + 'static>(&mut self, data: Vec<T>, label: Option<&str>) -> Result<(), DataLoaderError> {
+    pub fn add_column<T: ColumnType>(&mut self, data: Vec<T>, label: Option<&str>) -> Result<(), DataLoaderError> {
         let len: usize = data.len();
 
-        if self.columns.len() != 0 {
-            if self.columns.get(self.columns.keys().next().expect("No data in DataMatrix")).map_or(false, |first_col: &DataVector| first_col.len != len) {
-                return Err(DataLoaderError::RowCountMismatch);
-            }
+        if self.columns.values().next().is_some_and(|first_col: &DataVector| first_col.len != len) {
+            return Err(DataLoaderError::RowCountMismatch);
         }
 
         let mut column: DataVector = DataVector::from_vec(data)?;
@@ -41,11 +48,23 @@ impl DataMatrix {
         Ok(())
     }
 
+    // Adds an existing column, such as model predictions, keeping its nulls.
+    pub fn add_vector(&mut self, mut column: DataVector, label: &str) -> Result<(), DataLoaderError> {
+        if self.columns.values().next().is_some_and(|first_col: &DataVector| first_col.len != column.len) {
+            return Err(DataLoaderError::RowCountMismatch);
+        }
+        if self.columns.contains_key(label) {
+            return Err(DataLoaderError::DuplicateLabel);
+        }
+
+        column.add_label(label);
+        self.columns.insert(label.to_string(), column);
+        Ok(())
+    }
+
     pub fn set_index(&mut self, index: Vec<&str>) -> Result<(), DataLoaderError> {
-        if self.columns.len() != 0 {
-            if self.columns.get(self.columns.keys().next().expect("No data in DataMatrix")).map_or(false, |first_col: &DataVector| first_col.len != index.len()) {
-                return Err(DataLoaderError::RowCountMismatch);
-            }
+        if self.columns.values().next().is_some_and(|first_col: &DataVector| first_col.len != index.len()) {
+            return Err(DataLoaderError::RowCountMismatch);
         }
 
         if index.len() != index.iter().collect::<std::collections::HashSet<_>>().len() {
@@ -57,7 +76,7 @@ impl DataMatrix {
     }
 
     pub fn from_vec(vec: Vec<DataVector>) -> Result<Self, DataLoaderError> {
-        let mut columns = BTreeMap::new();
+        let mut columns = IndexMap::new();
         for data in vec.iter() {
             let key = data.label.clone().unwrap_or(columns.len().to_string());
             columns.insert(key, data.clone());
@@ -68,7 +87,7 @@ impl DataMatrix {
         })
     }
 
-    pub fn from_ndarray<T: Clone + Send + Sync + 'static>(arr: Array2<T>) -> Result<Self, DataLoaderError> {
+    pub fn from_ndarray<T: ColumnType>(arr: Array2<T>) -> Result<Self, DataLoaderError> {
         let mut mat: DataMatrix = DataMatrix::new();
         for row in arr.outer_iter() {
             let data_vector: Vec<T> = row.to_vec();
@@ -83,10 +102,14 @@ impl DataMatrix {
             return Err(DataLoaderError::HeterogeneousDataTypes)
         }
 
-        if self.columns.len() == 0 {
+        if self.columns.is_empty() {
             return Err(DataLoaderError::NoData);
         }
 
+        if let Some((label, _)) = self.columns.iter().find(|(_, column)| column.null_count() > 0) {
+            return Err(DataLoaderError::NullValues(label.clone()));
+        }
+
         let first_label: &String = self.columns.keys().next().expect("No data in DataMatrix");
 
         let num_rows: usize = self.columns[first_label].len;
@@ -103,16 +126,353 @@ impl DataMatrix {
             .map_err(|e: ndarray::ShapeError| DataLoaderError::GenericError(format!("{:#?}", e)))
     }
 
-    pub fn is_type_heterogeneous(&self) -> bool {
-        let first_label: &String = self.columns.keys().next().expect("No data in DataMatrix");
-        let first_dtype: Option<&'static str> = self.columns[first_label].dtype;
+    pub fn from_record_batch(batch: &RecordBatch) -> Result<Self, DataLoaderError> {
+        DataMatrix::from_record_batches(batch.schema(), std::slice::from_ref(batch))
+    }
+
+    pub fn from_record_batches(schema: SchemaRef, batches: &[RecordBatch]) -> Result<Self, DataLoaderError> {
+        let mut columns: IndexMap<String, DataVector> = IndexMap::new();
+        let mut index: Option<Vec<String>> = None;
+
+        for (col_idx, field) in schema.fields().iter().enumerate() {
+            let arrays: Vec<ArrayRef> = batches.iter().map(|batch| batch.column(col_idx).clone()).collect();
+
+            if field.name() == INDEX_COLUMN {
+                index = Some(DataVector::arrow_string_values(&arrays)?.into_iter().map(|v| v.unwrap_or_default()).collect());
+                continue;
+            }
+
+            if columns.contains_key(field.name()) {
+                return Err(DataLoaderError::DuplicateLabel);
+            }
+
+            let mut column: DataVector = DataVector::from_arrow_chunks(field.data_type(), &arrays)?;
+            column.add_label(field.name());
+            columns.insert(field.name().clone(), column);
+        }
+
+        let row_count: usize = batches.iter().map(|batch| batch.num_rows()).sum();
+        let index: Vec<String> = index.unwrap_or_else(|| (0..row_count).map(|i| format!("{}", i)).collect());
+
+        Ok(DataMatrix { columns, index })
+    }
+
+    pub fn to_record_batch(&self, include_index: bool) -> Result<RecordBatch, DataLoaderError> {
+        let mut fields: Vec<Field> = Vec::with_capacity(self.columns.len() + 1);
+        let mut arrays: Vec<ArrayRef> = Vec::with_capacity(self.columns.len() + 1);
+
+        if include_index && !self.index.is_empty() {
+            fields.push(Field::new(INDEX_COLUMN, DataType::Utf8, false));
+            arrays.push(Arc::new(StringArray::from(self.index.clone())));
+        }
+
+        for (label, column) in self.columns.iter() {
+            let array: ArrayRef = column.to_arrow()?;
+            fields.push(Field::new(label, array.data_type().clone(), column.null_count() > 0));
+            arrays.push(array);
+        }
+
+        RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)
+            .map_err(|e| DataLoaderError::GenericError(e.to_string()))
+    }
+
+    pub fn is_null(&self) -> DataMatrix {
+        let columns: IndexMap<String, DataVector> = self.columns
+            .iter()
+            .map(|(label, column)| {
+                let mut mask: DataVector = DataVector::from_nullable(None, (0..column.len).map(|row| Some(column.is_null(row))).collect());
+                mask.add_label(label);
+                (label.clone(), mask)
+            })
+            .collect();
+
+        DataMatrix { columns, index: self.index.clone() }
+    }
+
+    pub fn null_count(&self) -> IndexMap<String, usize> {
+        self.columns
+            .iter()
+            .map(|(label, column)| (label.clone(), column.null_count()))
+            .collect()
+    }
+
+    // Drops every row with a null in any of the given columns, or in any column at
+    // all when column_names is empty.
+    pub fn drop_nulls(&self, column_names: Vec<&str>) -> Result<DataMatrix, DataLoaderError> {
+        let checked: Vec<&DataVector> = if column_names.is_empty() {
+            self.columns.values().collect()
+        } else {
+            column_names
+                .iter()
+                .map(|&name| self.columns.get(name).ok_or(DataLoaderError::GenericError(format!("Column '{}' does not exist", name))))
+                .collect::<Result<Vec<&DataVector>, DataLoaderError>>()?
+        };
+
+        let rows: Vec<usize> = (0..self.num_rows()).filter(|&row| !checked.iter().any(|column| column.is_null(row))).collect();
+
+        self.take_rows(&rows)
+    }
+
+    // Fills nulls in every column holding values of type T; other columns are left as is.
+    pub fn fill_null<T: Clone + Send + Sync + 'static>(&self, value: T) -> Result<DataMatrix, DataLoaderError> {
+        let mut columns: IndexMap<String, DataVector> = IndexMap::new();
+
+        for (label, column) in self.columns.iter() {
+            let column: DataVector = if column.data.as_vec::<T>().is_some() {
+                column.fill_null(value.clone())?
+            } else {
+                column.clone()
+            };
+            columns.insert(label.clone(), column);
+        }
+
+        Ok(DataMatrix { columns, index: self.index.clone() })
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.columns.values().next().map(|col| col.len).unwrap_or(self.index.len())
+    }
+
+    // The index labels, or positional labels when the matrix was built without an index.
+    pub(crate) fn index_labels(&self) -> Vec<String> {
+        if self.index.is_empty() {
+            (0..self.num_rows()).map(|i| format!("{}", i)).collect()
+        } else {
+            self.index.clone()
+        }
+    }
 
-        if first_dtype.is_none() {
-            return false;
+    // Positional row selection, e.g. iloc(10..20) or iloc(..5).
+    pub fn iloc<R: RangeBounds<usize>>(&self, rows: R) -> Result<DataMatrix, DataLoaderError> {
+        let num_rows: usize = self.num_rows();
+
+        let start: usize = match rows.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.checked_add(1).ok_or(DataLoaderError::IndexError(start))?,
+            Bound::Unbounded => 0
+        };
+        let end: usize = match rows.end_bound() {
+            Bound::Included(&end) => end.checked_add(1).ok_or(DataLoaderError::IndexError(end))?,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => num_rows
+        };
+
+        if end > num_rows {
+            return Err(DataLoaderError::IndexError(end - 1));
+        }
+        if start > end {
+            return Err(DataLoaderError::GenericError(format!("Row range start {} is after its end {}", start, end)));
         }
 
+        self.take_rows(&(start..end).collect::<Vec<usize>>())
+    }
+
+    // Selects rows by their index labels, in the order given.
+    pub fn loc(&self, labels: Vec<&str>) -> Result<DataMatrix, DataLoaderError> {
+        let index: Vec<String> = self.index_labels();
+        let positions: HashMap<&str, usize> = index.iter().enumerate().map(|(row, label)| (label.as_str(), row)).collect();
+
+        let rows: Vec<usize> = labels
+            .iter()
+            .map(|&label| positions.get(label).copied().ok_or(DataLoaderError::GenericError(format!("Index label '{}' does not exist", label))))
+            .collect::<Result<Vec<usize>, DataLoaderError>>()?;
+
+        let mut selected: DataMatrix = self.take_rows(&rows)?;
+        selected.index = labels.iter().map(|label| label.to_string()).collect();
+
+        Ok(selected)
+    }
+
+    pub fn head(&self, n: usize) -> Result<DataMatrix, DataLoaderError> {
+        self.iloc(..n.min(self.num_rows()))
+    }
+
+    pub fn tail(&self, n: usize) -> Result<DataMatrix, DataLoaderError> {
+        let num_rows: usize = self.num_rows();
+        self.iloc(num_rows.saturating_sub(n)..)
+    }
+
+    // Keeps the rows where mask is true. Nulls in the mask count as false.
+    pub fn filter(&self, mask: &DataVector) -> Result<DataMatrix, DataLoaderError> {
+        if mask.dtype() != DType::Bool {
+            return Err(DataLoaderError::GenericError(format!("Filter mask must be a bool column, not {}", mask.dtype())));
+        }
+        if mask.len != self.num_rows() {
+            return Err(DataLoaderError::RowCountMismatch);
+        }
+
+        let rows: Vec<usize> = mask
+            .iter::<bool>()?
+            .enumerate()
+            .filter(|&(row, &keep)| keep && !mask.is_null(row))
+            .map(|(row, _)| row)
+            .collect();
+
+        self.take_rows(&rows)
+    }
+
+    // Keeps the rows where predicate holds for the value in column_name. Rows where
+    // that column is null are dropped.
+    pub fn filter_by<T: Clone + Send + Sync + 'static>(&self, column_name: &str, predicate: impl Fn(&T) -> bool) -> Result<DataMatrix, DataLoaderError> {
+        let column: &DataVector = self.columns
+            .get(column_name)
+            .ok_or(DataLoaderError::GenericError(format!("Column '{}' does not exist", column_name)))?;
+
+        let rows: Vec<usize> = column
+            .iter::<T>()?
+            .enumerate()
+            .filter(|&(row, value)| !column.is_null(row) && predicate(value))
+            .map(|(row, _)| row)
+            .collect();
+
+        self.take_rows(&rows)
+    }
+
+    // Stable multi-key sort. ascending holds either one flag for every key or one flag
+    // per key. Nulls go first or last regardless of the sort direction.
+    pub fn sort_by(&self, columns: Vec<&str>, ascending: Vec<bool>, nulls: NullPlacement) -> Result<DataMatrix, DataLoaderError> {
+        if ascending.len() != 1 && ascending.len() != columns.len() {
+            return Err(DataLoaderError::GenericError(format!("ascending had {} values for {} sort columns", ascending.len(), columns.len())));
+        }
+
+        let keys: Vec<(&DataVector, bool)> = columns
+            .iter()
+            .enumerate()
+            .map(|(i, &name)| {
+                let column: &DataVector = self.columns.get(name).ok_or(DataLoaderError::GenericError(format!("Column '{}' does not exist", name)))?;
+                Ok((column, ascending[if ascending.len() == 1 { 0 } else { i }]))
+            })
+            .collect::<Result<Vec<(&DataVector, bool)>, DataLoaderError>>()?;
+
+        let mut rows: Vec<usize> = (0..self.num_rows()).collect();
+        rows.sort_by(|&a, &b| {
+            for (column, ascending) in keys.iter() {
+                let ordering: Ordering = match (column.is_null(a), column.is_null(b)) {
+                    (true, true) => Ordering::Equal,
+                    (true, false) => if nulls == NullPlacement::First { Ordering::Less } else { Ordering::Greater },
+                    (false, true) => if nulls == NullPlacement::First { Ordering::Greater } else { Ordering::Less },
+                    (false, false) if *ascending => column.data.compare_rows(a, b),
+                    (false, false) => column.data.compare_rows(b, a)
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            Ordering::Equal
+        });
+
+        self.take_rows(&rows)
+    }
+
+    // Keeps the first occurrence of each distinct row, comparing only the subset
+    // columns or every column when subset is empty.
+    pub fn drop_duplicates(&self, subset: Vec<&str>) -> Result<DataMatrix, DataLoaderError> {
+        let checked: Vec<&DataVector> = if subset.is_empty() {
+            self.columns.values().collect()
+        } else {
+            subset
+                .iter()
+                .map(|&name| self.columns.get(name).ok_or(DataLoaderError::GenericError(format!("Column '{}' does not exist", name))))
+                .collect::<Result<Vec<&DataVector>, DataLoaderError>>()?
+        };
+
+        let mut seen: HashSet<Vec<Option<String>>> = HashSet::new();
+        let rows: Vec<usize> = (0..self.num_rows())
+            .filter(|&row| {
+                let key: Vec<Option<String>> = checked
+                    .iter()
+                    .map(|column| if column.is_null(row) { None } else { column.data.format_debug(row) })
+                    .collect();
+                seen.insert(key)
+            })
+            .collect();
+
+        self.take_rows(&rows)
+    }
+
+    pub fn sample(&self, size: SampleSize, with_replacement: bool, seed: Option<u64>) -> Result<DataMatrix, DataLoaderError> {
+        let num_rows: usize = self.num_rows();
+
+        let n: usize = match size {
+            SampleSize::N(n) => n,
+            SampleSize::Frac(frac) if frac >= 0.0 => (frac * num_rows as f64).round() as usize,
+            SampleSize::Frac(frac) => return Err(DataLoaderError::GenericError(format!("Sample fraction must not be negative, got {}", frac)))
+        };
+
+        let mut rng: Rng = Rng::new(seed);
+
+        let rows: Vec<usize> = if with_replacement {
+            if num_rows == 0 && n > 0 {
+                return Err(DataLoaderError::NoData);
+            }
+            (0..n).map(|_| rng.below(num_rows)).collect()
+        } else {
+            if n > num_rows {
+                return Err(DataLoaderError::GenericError(format!("Cannot take {} rows from {} without replacement", n, num_rows)));
+            }
+            let mut rows: Vec<usize> = (0..num_rows).collect();
+            rng.shuffle(&mut rows);
+            rows.truncate(n);
+            rows
+        };
+
+        self.take_rows(&rows)
+    }
+
+    pub fn shuffle(&self, seed: Option<u64>) -> Result<DataMatrix, DataLoaderError> {
+        let mut rows: Vec<usize> = (0..self.num_rows()).collect();
+        Rng::new(seed).shuffle(&mut rows);
+        self.take_rows(&rows)
+    }
+
+    pub fn group_by(&self, keys: Vec<&str>) -> Result<GroupBy<'_>, DataLoaderError> {
+        GroupBy::new(self, keys)
+    }
+
+    pub fn take_rows(&self, rows: &[usize]) -> Result<DataMatrix, DataLoaderError> {
+        let mut columns: IndexMap<String, DataVector> = IndexMap::new();
+        for (label, column) in self.columns.iter() {
+            columns.insert(label.clone(), column.take(rows)?);
+        }
+
+        let index: Vec<String> = if self.index.is_empty() {
+            Vec::new()
+        } else {
+            rows.iter().map(|&row| self.index.get(row).cloned().ok_or(DataLoaderError::IndexError(row))).collect::<Result<Vec<String>, DataLoaderError>>()?
+        };
+
+        Ok(DataMatrix { columns, index })
+    }
+
+    pub fn column_names(&self) -> Vec<String> {
+        self.columns.keys().cloned().collect()
+    }
+
+    // Reorders the columns to match column_names. The matrix must hold exactly those
+    // columns; anything missing or unexpected is reported rather than silently shifting
+    // feature positions.
+    pub fn align_columns(&self, column_names: &[String]) -> Result<DataMatrix, DataLoaderError> {
+        let missing: Vec<String> = column_names.iter().filter(|name| !self.columns.contains_key(*name)).cloned().collect();
+        let unexpected: Vec<String> = self.columns.keys().filter(|name| !column_names.contains(name)).cloned().collect();
+
+        if !missing.is_empty() || !unexpected.is_empty() {
+            return Err(DataLoaderError::ColumnMismatch { missing, unexpected });
+        }
+
+        let columns: IndexMap<String, DataVector> = column_names
+            .iter()
+            .map(|name| (name.clone(), self.columns[name].clone()))
+            .collect();
+
+        Ok(DataMatrix { columns, index: self.index.clone() })
+    }
+
+    pub fn is_type_heterogeneous(&self) -> bool {
+        let first_label: &String = self.columns.keys().next().expect("No data in DataMatrix");
+        let first_dtype: DType = self.columns[first_label].dtype();
+
         for &column in &self.columns.values().collect::<Vec<&DataVector>>() {
-            if column.dtype != first_dtype {
+            if column.dtype() != first_dtype {
                 return true;
             }
         }
@@ -166,24 +526,23 @@ impl DataMatrix {
     }
 }
 
+impl Default for DataMatrix {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl fmt::Display for DataMatrix {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut col_labels: Vec<&String> = self.columns.keys().collect();
-        col_labels.sort();
+        let col_labels: Vec<&String> = self.columns.keys().collect();
+
+        let num_rows = self.columns.values().next().map(|col| col.len).unwrap_or(0);
 
         let mut col_widths: Vec<usize> = col_labels.iter().map(|label| label.len()).collect();
         for (i, label) in col_labels.iter().enumerate() {
             if let Some(col) = self.columns.get(*label) {
-                if let Some(vec) = col.data.downcast_ref::<Vec<String>>() {
-                    let max_data_len = vec.iter().map(|s| s.len()).max().unwrap_or(0);
-                    col_widths[i] = col_widths[i].max(max_data_len);
-                } else if let Some(vec) = col.data.downcast_ref::<Vec<f64>>() {
-                    let max_data_len = vec.iter().map(|v| format!("{:.3}", v).len()).max().unwrap_or(0);
-                    col_widths[i] = col_widths[i].max(max_data_len);
-                } else if let Some(vec) = col.data.downcast_ref::<Vec<i32>>() {
-                    let max_data_len = vec.iter().map(|v| v.to_string().len()).max().unwrap_or(0);
-                    col_widths[i] = col_widths[i].max(max_data_len);
-                }
+                let max_data_len = (0..col.len).map(|row| col.format_value(row).len()).max().unwrap_or(0);
+                col_widths[i] = col_widths[i].max(max_data_len);
             }
         }
 
@@ -201,41 +560,150 @@ impl fmt::Display for DataMatrix {
         }
         writeln!(f)?;
 
-        let num_rows = self.columns.values().next().map(|col| col.len).unwrap_or(0);
         for row in 0..num_rows {
             let index = self.index.get(row).map(String::as_str).unwrap_or("");
             write!(f, "| {:^index_width$} |", index, index_width = index_width)?;
 
             for (label, width) in col_labels.iter().zip(&col_widths) {
-                let col = &self.columns[*label];
-                if let Some(dtype) = col.dtype {
-                    match dtype {
-                        "bool" => {
-                            let vec = col.data.downcast_ref::<Vec<bool>>().unwrap();
-                            let value = vec.get(row).map(|v| v.to_string()).unwrap_or_else(|| "".to_string());
-                            write!(f, " {:^width$} |", value, width = width)?;
-                        }
-                        "i64" => {
-                            let vec = col.data.downcast_ref::<Vec<i64>>().unwrap();
-                            let value = vec.get(row).map(|v| v.to_string()).unwrap_or_else(|| "".to_string());
-                            write!(f, " {:^width$} |", value, width = width)?;
-                        }
-                        "f64" => {
-                            let vec = col.data.downcast_ref::<Vec<f64>>().unwrap();
-                            let value = vec.get(row).map(|v| format!("{:.3}", v)).unwrap_or_else(|| "".to_string());
-                            write!(f, " {:^width$} |", value, width = width)?;
-                        }
-                        "alloc::string::String" | "String" => {
-                            let vec = col.data.downcast_ref::<Vec<String>>().unwrap();
-                            let value = vec.get(row).map(String::as_str).unwrap_or("");
-                            write!(f, " {:^width$} |", value, width = width)?;
-                        }
-                        _ => writeln!(f, "Unknown dtype '{}'", dtype)?,
-                    }
-                }
+                let value = self.columns[*label].format_value(row);
+                write!(f, " {:^width$} |", value, width = width)?;
             }
             writeln!(f)?;
         }
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iloc_rejects_bounds_at_usize_max() {
+        let matrix: DataMatrix = DataMatrix::from_vec(vec![DataVector::from_nullable(Some("a".to_string()), vec![Some(1i64), Some(2)])]).unwrap();
+
+        assert!(matches!(matrix.iloc(..=usize::MAX), Err(DataLoaderError::IndexError(usize::MAX))));
+        assert!(matches!(matrix.iloc((Bound::Excluded(usize::MAX), Bound::Unbounded)), Err(DataLoaderError::IndexError(usize::MAX))));
+        assert_eq!(matrix.iloc(1..=1).unwrap().get_column("a").unwrap().to_options::<i64>().unwrap(), vec![Some(2)]);
+    }
+
+    #[test]
+    fn loc_uses_positional_labels_without_an_index() {
+        let matrix: DataMatrix = DataMatrix::from_vec(vec![DataVector::from_nullable(Some("a".to_string()), vec![Some(1i64), Some(2), Some(3)])]).unwrap();
+
+        let selected: DataMatrix = matrix.loc(vec!["2", "0"]).unwrap();
+        assert_eq!(selected.index, vec!["2".to_string(), "0".to_string()]);
+        assert_eq!(selected.get_column("a").unwrap().to_options::<i64>().unwrap(), vec![Some(3), Some(1)]);
+        assert!(matrix.loc(vec!["3"]).is_err());
+    }
+
+    fn unsorted() -> DataMatrix {
+        let mut matrix: DataMatrix = DataMatrix::from_vec(vec![
+            DataVector::from_nullable(Some("a".to_string()), vec![Some(2i64), Some(1), None, Some(2), Some(1), Some(2)]),
+            DataVector::from_nullable(Some("b".to_string()), ["x", "y", "z", "y", "x", "x"].iter().map(|b| Some(b.to_string())).collect())
+        ])
+        .unwrap();
+        matrix.set_index(vec!["r0", "r1", "r2", "r3", "r4", "r5"]).unwrap();
+        matrix
+    }
+
+    #[test]
+    fn sort_by_is_stable_across_mixed_directions_and_null_placements() {
+        let matrix: DataMatrix = unsorted();
+
+        let sorted: DataMatrix = matrix.sort_by(vec!["a", "b"], vec![true, false], NullPlacement::Last).unwrap();
+        assert_eq!(sorted.index, vec!["r1", "r4", "r3", "r0", "r5", "r2"]);
+
+        let sorted: DataMatrix = matrix.sort_by(vec!["a", "b"], vec![false], NullPlacement::First).unwrap();
+        assert_eq!(sorted.index, vec!["r2", "r3", "r0", "r5", "r1", "r4"]);
+        assert_eq!(sorted.get_column("a").unwrap().to_options::<i64>().unwrap(), vec![None, Some(2), Some(2), Some(2), Some(1), Some(1)]);
+
+        assert!(matrix.sort_by(vec!["a", "b"], vec![true, true, true], NullPlacement::Last).is_err());
+    }
+
+    #[test]
+    fn drop_duplicates_keeps_first_occurrences() {
+        let matrix: DataMatrix = unsorted();
+
+        assert_eq!(matrix.drop_duplicates(vec![]).unwrap().index, vec!["r0", "r1", "r2", "r3", "r4"]);
+        assert_eq!(matrix.drop_duplicates(vec!["a"]).unwrap().index, vec!["r0", "r1", "r2"]);
+        assert_eq!(matrix.drop_duplicates(vec!["b"]).unwrap().index, vec!["r0", "r1", "r2"]);
+    }
+
+    #[test]
+    fn seeded_sample_and_shuffle_are_reproducible() {
+        let matrix: DataMatrix = unsorted();
+
+        let sample = |with_replacement: bool, seed: u64| matrix.sample(SampleSize::N(4), with_replacement, Some(seed)).unwrap().index;
+        assert_eq!(sample(false, 7), sample(false, 7));
+        assert_eq!(sample(true, 7), sample(true, 7));
+        assert_eq!(sample(false, 7).iter().collect::<HashSet<&String>>().len(), 4);
+
+        let shuffled: Vec<String> = matrix.shuffle(Some(42)).unwrap().index;
+        assert_eq!(shuffled, matrix.shuffle(Some(42)).unwrap().index);
+
+        let mut rows: Vec<String> = shuffled.clone();
+        rows.sort();
+        assert_eq!(rows, matrix.index);
+    }
+
+    #[test]
+    fn sample_sizes_are_checked() {
+        let matrix: DataMatrix = unsorted();
+
+        assert_eq!(matrix.sample(SampleSize::Frac(0.5), false, Some(1)).unwrap().num_rows(), 3);
+        assert!(matrix.sample(SampleSize::Frac(-0.1), false, Some(1)).is_err());
+        assert!(matrix.sample(SampleSize::N(7), false, Some(1)).is_err());
+        assert_eq!(matrix.sample(SampleSize::N(7), true, Some(1)).unwrap().num_rows(), 7);
+
+        let empty: DataMatrix = matrix.iloc(0..0).unwrap();
+        assert!(matches!(empty.sample(SampleSize::N(1), true, Some(1)), Err(DataLoaderError::NoData)));
+        assert_eq!(empty.sample(SampleSize::N(0), true, Some(1)).unwrap().num_rows(), 0);
+    }
+
+    fn with_nulls() -> DataMatrix {
+        let mut matrix: DataMatrix = DataMatrix::from_vec(vec![
+            DataVector::from_nullable(Some("a".to_string()), vec![Some(1i64), None, Some(3), None]),
+            DataVector::from_nullable(Some("b".to_string()), vec![Some("x".to_string()), Some("y".to_string()), None, None]),
+            DataVector::from_nullable(Some("c".to_string()), vec![Some(0.5), Some(1.5), Some(2.5), Some(3.5)])
+        ])
+        .unwrap();
+        matrix.set_index(vec!["r0", "r1", "r2", "r3"]).unwrap();
+        matrix
+    }
+
+    #[test]
+    fn is_null_and_null_count_report_missing_values() {
+        let matrix: DataMatrix = with_nulls();
+        let mask: DataMatrix = matrix.is_null();
+
+        assert_eq!(mask.index, matrix.index);
+        assert_eq!(mask.get_column("a").unwrap().to_options::<bool>().unwrap(), vec![Some(false), Some(true), Some(false), Some(true)]);
+        assert_eq!(mask.get_column("c").unwrap().null_count(), 0);
+        assert_eq!(matrix.null_count().into_iter().collect::<Vec<(String, usize)>>(), vec![("a".to_string(), 2), ("b".to_string(), 2), ("c".to_string(), 0)]);
+    }
+
+    #[test]
+    fn drop_nulls_checks_the_given_columns_or_all_of_them() {
+        let matrix: DataMatrix = with_nulls();
+
+        assert_eq!(matrix.drop_nulls(vec![]).unwrap().index, vec!["r0"]);
+        assert_eq!(matrix.drop_nulls(vec!["a"]).unwrap().index, vec!["r0", "r2"]);
+        assert_eq!(matrix.drop_nulls(vec!["c"]).unwrap().index, matrix.index);
+        assert!(matrix.drop_nulls(vec!["missing"]).is_err());
+        assert_eq!(matrix.get_column("b").unwrap().drop_nulls().unwrap().to_options::<String>().unwrap(), vec![Some("x".to_string()), Some("y".to_string())]);
+    }
+
+    #[test]
+    fn fill_null_only_touches_columns_of_the_value_type() {
+        let filled: DataMatrix = with_nulls().fill_null(0i64).unwrap();
+
+        assert_eq!(filled.get_column("a").unwrap().to_options::<i64>().unwrap(), vec![Some(1), Some(0), Some(3), Some(0)]);
+        assert_eq!(filled.get_column("a").unwrap().null_count(), 0);
+        assert_eq!(filled.get_column("b").unwrap().null_count(), 2);
+
+        let column: DataVector = with_nulls().get_column("b").unwrap();
+        assert_eq!(column.fill_null("?".to_string()).unwrap().to_options::<String>().unwrap(), vec![Some("x".to_string()), Some("y".to_string()), Some("?".to_string()), Some("?".to_string())]);
+        assert!(column.fill_null(0i64).is_err());
+    }
+}
@@ -1,160 +1,479 @@
-use std::any::Any;
+use std::sync::Arc;
 
+use arrow_array::{cast::AsArray, types::{Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, UInt16Type, UInt32Type, UInt64Type, UInt8Type}, Array, ArrayRef, BooleanArray, DictionaryArray, DurationMicrosecondArray, Float32Array, Float64Array, Int32Array, Int64Array, StringArray, TimestampMicrosecondArray};
+use arrow_cast::display::{ArrayFormatter, FormatOptions};
+use arrow_schema::{DataType, TimeUnit};
 use ndarray::Array1;
+use veracity_types::dtype::DType;
 
-use crate::enums::error_types::DataLoaderError;
+use crate::{column_data::{Categorical, ColumnData, ColumnType}, enums::error_types::DataLoaderError};
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "crate::serde_validation::RawDataVector"))]
 pub struct DataVector {
     pub label: Option<String>,
-    pub data: Box<dyn Any + Send + Sync>,
+    pub data: ColumnData,
     pub len: usize,
-    pub dtype: Option<&'static str>
+    pub validity: Option<Vec<bool>>
 }
 
 impl DataVector {
     pub fn new() -> Self {
         DataVector { 
             label: None, 
-            data: Box::new(0),
+            data: ColumnData::F64(Vec::new()),
             len: 0,
-            dtype: None
+            validity: None
         }
     }
 
-    pub fn from_vec<T: Clone + Send + Sync + 'static>(vec: Vec<T>) -> Result<Self, DataLoaderError> {
+    pub fn from_vec<T: ColumnType>(vec: Vec<T>) -> Result<Self, DataLoaderError> {
         let len: usize = vec.len();
-        let dtype: Option<&'static str> = Some(std::any::type_name::<T>());
+        let data: ColumnData = T::into_column(vec);
         Ok(DataVector {
             label: None,
-            data: Box::new(vec),
+            data,
             len,
-            dtype
+            validity: None
         })
     }
 
+    // Null slots hold T::default() in the underlying Vec<T>; validity is the source
+    // of truth for which values are present.
+    pub fn from_options<T: ColumnType>(values: Vec<Option<T>>) -> Result<Self, DataLoaderError> {
+        Ok(Self::from_nullable(None, values))
+    }
+
+    pub fn dtype(&self) -> DType {
+        self.data.dtype()
+    }
+
+    // Categorical columns are decoded, so they can be read back as String.
+    pub fn to_options<T: Clone + Send + Sync + 'static>(&self) -> Result<Vec<Option<T>>, DataLoaderError> {
+        if let ColumnData::Categorical(_) = self.data {
+            return self.to_utf8()?.to_options();
+        }
+
+        Ok(self
+            .iter::<T>()?
+            .enumerate()
+            .map(|(i, value)| if self.is_null(i) { None } else { Some(value.clone()) })
+            .collect())
+    }
+
     pub fn to_vec<T: Clone + Send + Sync + 'static>(&self) -> Result<Vec<T>, DataLoaderError> {
-        self.data.downcast_ref::<Vec<T>>().ok_or(
-            DataLoaderError::GenericError("Failed to cast data to Vec<T>".to_string())
+        if let ColumnData::Categorical(_) = self.data {
+            return self.to_utf8()?.to_vec();
+        }
+
+        self.data.as_vec::<T>().ok_or(
+            DataLoaderError::GenericError(format!("Failed to cast {} data to Vec<{}>", self.dtype(), std::any::type_name::<T>()))
         ).cloned()
     }
 
-    pub fn from_ndarray<T: Clone + Send + Sync + 'static>(arr: Array1<T>) -> Result<Self, DataLoaderError> {
-        let dtype: Option<&'static str> = Some(std::any::type_name::<T>());
-        Ok(DataVector {
-            label: None,
-            data: Box::new(arr.to_vec()),
-            len: arr.len(),
-            dtype
-        })
+    pub fn from_ndarray<T: ColumnType>(arr: Array1<T>) -> Result<Self, DataLoaderError> {
+        Self::from_vec(arr.to_vec())
     }
 
     pub fn to_ndarray<T: Clone + Send + Sync + 'static>(&self) -> Result<Array1<T>, DataLoaderError> {
+        if self.null_count() > 0 {
+            return Err(DataLoaderError::NullValues(self.label.clone().unwrap_or_default()));
+        }
+
         let vec: Vec<T> = self.to_vec()?;
         Ok(Array1::from_vec(vec))
     }
 
+    pub fn from_arrow(array: &ArrayRef) -> Result<Self, DataLoaderError> {
+        Self::from_arrow_chunks(array.data_type(), std::slice::from_ref(array))
+    }
+
+    pub fn to_arrow(&self) -> Result<ArrayRef, DataLoaderError> {
+        match self.dtype() {
+            DType::Bool => Ok(Arc::new(BooleanArray::from(self.to_options::<bool>()?))),
+            DType::I32 => Ok(Arc::new(Int32Array::from(self.to_options::<i32>()?))),
+            DType::I64 => Ok(Arc::new(Int64Array::from(self.to_options::<i64>()?))),
+            DType::F32 => Ok(Arc::new(Float32Array::from(self.to_options::<f32>()?))),
+            DType::F64 => Ok(Arc::new(Float64Array::from(self.to_options::<f64>()?))),
+            DType::Utf8 => Ok(Arc::new(StringArray::from(self.to_options::<String>()?))),
+            DType::Categorical => {
+                let ColumnData::Categorical(categorical) = &self.data else { unreachable!() };
+                let keys: Int32Array = (0..self.len).map(|i| if self.is_null(i) { None } else { Some(categorical.codes[i] as i32) }).collect();
+                let values: ArrayRef = Arc::new(StringArray::from(categorical.categories.clone()));
+                let array: DictionaryArray<Int32Type> = DictionaryArray::try_new(keys, values).map_err(|e| DataLoaderError::GenericError(e.to_string()))?;
+                Ok(Arc::new(array))
+            },
+            DType::Datetime => Ok(Arc::new(TimestampMicrosecondArray::from(self.to_options::<i64>()?))),
+            DType::Duration => Ok(Arc::new(DurationMicrosecondArray::from(self.to_options::<i64>()?)))
+        }
+    }
+
+    // Dictionary encodes a string column against its sorted distinct values.
+    pub fn to_categorical(&self) -> Result<DataVector, DataLoaderError> {
+        match &self.data {
+            ColumnData::Categorical(_) => Ok(self.clone()),
+            ColumnData::Utf8(_) => Ok(Self::from_categorical(self.label.clone(), self.to_options::<String>()?)),
+            _ => Err(DataLoaderError::GenericError(format!("Only utf8 columns can be converted to categorical, not {}", self.dtype())))
+        }
+    }
+
+    // Dictionary encodes against a fixed set of categories, e.g. the ones a model saw
+    // during training. Values outside categories are an error.
+    pub fn to_categorical_with(&self, categories: Vec<String>) -> Result<DataVector, DataLoaderError> {
+        if !matches!(self.dtype(), DType::Utf8 | DType::Categorical) {
+            return Err(DataLoaderError::GenericError(format!("Only utf8 columns can be converted to categorical, not {}", self.dtype())));
+        }
+
+        let categorical: Categorical = Categorical::encode_with(&self.to_options::<String>()?, categories)?;
+
+        Ok(DataVector {
+            label: self.label.clone(),
+            data: ColumnData::Categorical(categorical),
+            len: self.len,
+            validity: self.validity.clone()
+        })
+    }
+
+    pub fn to_utf8(&self) -> Result<DataVector, DataLoaderError> {
+        match &self.data {
+            ColumnData::Utf8(_) => Ok(self.clone()),
+            ColumnData::Categorical(categorical) => {
+                let values: Vec<Option<String>> = (0..self.len).map(|i| if self.is_null(i) { None } else { categorical.value(i).cloned() }).collect();
+                Ok(Self::from_nullable(self.label.clone(), values))
+            },
+            _ => Err(DataLoaderError::GenericError(format!("Only categorical columns can be converted to utf8, not {}", self.dtype())))
+        }
+    }
+
+    pub fn categories(&self) -> Option<&[String]> {
+        match &self.data {
+            ColumnData::Categorical(categorical) => Some(&categorical.categories),
+            _ => None
+        }
+    }
+
     pub fn add_label(&mut self, label: impl AsRef<str>) {
         self.label = Some(label.as_ref().to_string());
     }
 
+    pub fn is_null(&self, index: usize) -> bool {
+        self.validity.as_ref().is_some_and(|validity| !validity[index])
+    }
+
+    pub fn null_count(&self) -> usize {
+        self.validity.as_ref().map_or(0, |validity| validity.iter().filter(|&&valid| !valid).count())
+    }
+
+    pub fn drop_nulls(&self) -> Result<DataVector, DataLoaderError> {
+        let rows: Vec<usize> = (0..self.len).filter(|&i| !self.is_null(i)).collect();
+        self.take(&rows)
+    }
+
+    pub fn fill_null<T: Clone + Send + Sync + 'static>(&self, value: T) -> Result<DataVector, DataLoaderError> {
+        let mut filled: DataVector = self.clone();
+        if let Some(validity) = filled.validity.take() {
+            for (slot, valid) in filled.iter_mut::<T>()?.zip(validity) {
+                if !valid {
+                    *slot = value.clone();
+                }
+            }
+        }
+        Ok(filled)
+    }
+
+    // Appends other's rows. Both vectors must hold the same dtype.
+    pub fn append(&mut self, other: &DataVector) -> Result<(), DataLoaderError> {
+        if self.dtype() != other.dtype() {
+            return Err(DataLoaderError::GenericError(format!("Cannot append {} data to a {} column", other.dtype(), self.dtype())));
+        }
+
+        if self.validity.is_some() || other.validity.is_some() {
+            let mut validity: Vec<bool> = self.validity.take().unwrap_or_else(|| vec![true; self.len]);
+            validity.extend(other.validity.clone().unwrap_or_else(|| vec![true; other.len]));
+            self.validity = Some(validity);
+        }
+
+        self.data.extend(&other.data);
+        self.len += other.len;
+        Ok(())
+    }
+
+    pub(crate) fn nulls(label: Option<String>, dtype: DType, len: usize) -> Self {
+        DataVector {
+            label,
+            data: ColumnData::empty(dtype).take_optional(&vec![None; len]),
+            len,
+            validity: if len > 0 { Some(vec![false; len]) } else { None }
+        }
+    }
+
+    pub(crate) fn from_categorical(label: Option<String>, values: Vec<Option<String>>) -> Self {
+        let validity: Vec<bool> = values.iter().map(|v| v.is_some()).collect();
+
+        DataVector {
+            label,
+            data: ColumnData::Categorical(Categorical::encode(&values)),
+            len: values.len(),
+            validity: if validity.contains(&false) { Some(validity) } else { None }
+        }
+    }
+
+    pub(crate) fn from_nullable<T: ColumnType>(label: Option<String>, values: Vec<Option<T>>) -> Self {
+        let len: usize = values.len();
+        let validity: Vec<bool> = values.iter().map(|v| v.is_some()).collect();
+        let data: Vec<T> = values.into_iter().map(|v| v.unwrap_or_default()).collect();
+
+        DataVector {
+            label,
+            data: T::into_column(data),
+            len,
+            validity: if validity.contains(&false) { Some(validity) } else { None }
+        }
+    }
+
+    pub(crate) fn take(&self, rows: &[usize]) -> Result<DataVector, DataLoaderError> {
+        if let Some(&row) = rows.iter().find(|&&row| row >= self.len) {
+            return Err(DataLoaderError::IndexError(row));
+        }
+
+        let validity: Option<Vec<bool>> = self.validity
+            .as_ref()
+            .map(|validity| rows.iter().map(|&row| validity[row]).collect::<Vec<bool>>())
+            .filter(|validity| validity.contains(&false));
+
+        Ok(DataVector {
+            label: self.label.clone(),
+            data: self.data.take(rows),
+            len: rows.len(),
+            validity
+        })
+    }
+
+    // Like take, but rows given as None become nulls.
+    pub(crate) fn take_optional(&self, rows: &[Option<usize>]) -> Result<DataVector, DataLoaderError> {
+        if let Some(row) = rows.iter().flatten().find(|&&row| row >= self.len) {
+            return Err(DataLoaderError::IndexError(*row));
+        }
+
+        let validity: Vec<bool> = rows.iter().map(|row| row.is_some_and(|row| !self.is_null(row))).collect();
+
+        Ok(DataVector {
+            label: self.label.clone(),
+            data: self.data.take_optional(rows),
+            len: rows.len(),
+            validity: if validity.contains(&false) { Some(validity) } else { None }
+        })
+    }
+
+    pub(crate) fn format_value(&self, index: usize) -> String {
+        if self.is_null(index) {
+            return "null".to_string();
+        }
+
+        self.data.format_value(index).unwrap_or_default()
+    }
+
     pub fn iter<T: Clone + Send + Sync + 'static>(&self) -> Result<impl Iterator<Item = &T>, DataLoaderError> {
         self.data
-            .downcast_ref::<Vec<T>>()
+            .as_vec::<T>()
             .map(|vec| vec.iter())
             .ok_or(DataLoaderError::GenericError(
-                "Failed to downcast to Vec<T> in iter".to_string(),
+                format!("Failed to downcast {} data to Vec<{}> in iter", self.dtype(), std::any::type_name::<T>()),
             ))
     }
 
     pub fn iter_mut<T: 'static>(&mut self) -> Result<impl Iterator<Item = &mut T>, DataLoaderError> {
+        let dtype: DType = self.dtype();
         self.data
-            .downcast_mut::<Vec<T>>()
+            .as_vec_mut::<T>()
             .map(|vec: &mut Vec<T>| vec.iter_mut())
             .ok_or(DataLoaderError::GenericError(
-                "Failed to downcast to Vec<T> for iter_mut".to_string(),
+                format!("Failed to downcast {} data to Vec<{}> for iter_mut", dtype, std::any::type_name::<T>()),
             ))
     }
 
     pub fn get<T: Clone + Send + Sync + 'static>(&self, index: usize) -> Result<Option<&T>, DataLoaderError> {
         let vec: &Vec<T> = self
             .data
-            .downcast_ref::<Vec<T>>()
+            .as_vec::<T>()
             .ok_or(DataLoaderError::GenericError(
-                "Failed to downcast to Vec<T> in get".to_string(),
+                format!("Failed to downcast {} data to Vec<{}> in get", self.dtype(), std::any::type_name::<T>()),
             ))?;
         Ok(vec.get(index))
     }
 
     pub fn get_mut<T: Clone + Send + Sync + 'static>(&mut self, index: usize) -> Result<Option<&mut T>, DataLoaderError> {
+        let dtype: DType = self.dtype();
         let vec: &mut Vec<T> = self
             .data
-            .downcast_mut::<Vec<T>>()
+            .as_vec_mut::<T>()
             .ok_or(DataLoaderError::GenericError(
-                "Failed to downcast to Vec<T> in get_mut".to_string(),
+                format!("Failed to downcast {} data to Vec<{}> in get_mut", dtype, std::any::type_name::<T>()),
             ))?;
         Ok(vec.get_mut(index))
     }
 
-    
-}
+    pub(crate) fn from_arrow_chunks(data_type: &DataType, arrays: &[ArrayRef]) -> Result<Self, DataLoaderError> {
+        match data_type {
+            DataType::Boolean => {
+                let values: Vec<Option<bool>> = Self::arrow_collect_values(arrays, |array, i| array.as_boolean().value(i));
+                return Ok(Self::from_nullable(None, values));
+            },
+            DataType::Int32 => {
+                let values: Vec<Option<i32>> = Self::arrow_collect_values(arrays, |array, i| array.as_primitive::<Int32Type>().value(i));
+                return Ok(Self::from_nullable(None, values));
+            },
+            DataType::Float32 => {
+                let values: Vec<Option<f32>> = Self::arrow_collect_values(arrays, |array, i| array.as_primitive::<Float32Type>().value(i));
+                return Ok(Self::from_nullable(None, values));
+            },
+            DataType::Timestamp(_, _) | DataType::Date32 | DataType::Date64 => {
+                return Ok(Self::from_micros(None, Self::arrow_temporal_values(arrays)?, DType::Datetime));
+            },
+            DataType::Duration(_) => {
+                return Ok(Self::from_micros(None, Self::arrow_temporal_values(arrays)?, DType::Duration));
+            },
+            DataType::Dictionary(_, value_type) if matches!(value_type.as_ref(), DataType::Utf8 | DataType::LargeUtf8) => {
+                return Ok(Self::from_categorical(None, Self::arrow_string_values(arrays)?));
+            },
+            _ => {}
+        }
 
-impl Clone for DataVector {
-    fn clone(&self) -> Self {
-        let label = self.label.clone();
-        let dtype = self.dtype;
-
-        let len = self.len;
-
-        let data = if let Some(data_ref) = self.data.downcast_ref::<Vec<i64>>() {
-            Box::new(data_ref.clone()) as Box<dyn Any + Send + Sync>
-        } else if let Some(data_ref) = self.data.downcast_ref::<Vec<f64>>() {
-            Box::new(data_ref.clone()) as Box<dyn Any + Send + Sync>
-        } else if let Some(data_ref) = self.data.downcast_ref::<Vec<bool>>() {
-            Box::new(data_ref.clone()) as Box<dyn Any + Send + Sync>
-        } else if let Some(data_ref) = self.data.downcast_ref::<Vec<String>>() {
-            Box::new(data_ref.clone()) as Box<dyn Any + Send + Sync>
-        } else {
-            unimplemented!("Clone not implemented for this data type")
-        };
+        if data_type.is_integer() && let Some(values) = Self::arrow_integer_values(arrays) {
+            return Ok(Self::from_nullable(None, values));
+        }
 
-        DataVector {
-            label,
-            data,
-            len,
-            dtype,
+        if data_type.is_integer() || data_type.is_floating() {
+            return Ok(Self::from_nullable(None, Self::arrow_float_values(arrays)?));
+        }
+
+        Ok(Self::from_nullable(None, Self::arrow_string_values(arrays)?))
+    }
+
+    fn arrow_collect_values<T, F>(arrays: &[ArrayRef], value: F) -> Vec<Option<T>>
+    where
+        F: Fn(&dyn Array, usize) -> T
+    {
+        let mut values: Vec<Option<T>> = Vec::with_capacity(arrays.iter().map(|array| array.len()).sum());
+        for array in arrays {
+            for i in 0..array.len() {
+                values.push(if array.is_null(i) { None } else { Some(value(array.as_ref(), i)) });
+            }
         }
+        values
+    }
+
+    fn arrow_integer_values(arrays: &[ArrayRef]) -> Option<Vec<Option<i64>>> {
+        let values: Vec<Option<Option<i64>>> = Self::arrow_collect_values(arrays, |array, i| match array.data_type() {
+            DataType::Int8 => Some(array.as_primitive::<Int8Type>().value(i) as i64),
+            DataType::Int16 => Some(array.as_primitive::<Int16Type>().value(i) as i64),
+            DataType::Int32 => Some(array.as_primitive::<Int32Type>().value(i) as i64),
+            DataType::Int64 => Some(array.as_primitive::<Int64Type>().value(i)),
+            DataType::UInt8 => Some(array.as_primitive::<UInt8Type>().value(i) as i64),
+            DataType::UInt16 => Some(array.as_primitive::<UInt16Type>().value(i) as i64),
+            DataType::UInt32 => Some(array.as_primitive::<UInt32Type>().value(i) as i64),
+            DataType::UInt64 => i64::try_from(array.as_primitive::<UInt64Type>().value(i)).ok(),
+            _ => None
+        });
+
+        values.into_iter().map(|v| v.map_or(Some(None), |v| v.map(Some))).collect()
+    }
+
+    // Other numeric arrays, such as Float16 or u64 beyond the i64 range, are cast to
+    // Float64 first.
+    fn arrow_float_values(arrays: &[ArrayRef]) -> Result<Vec<Option<f64>>, DataLoaderError> {
+        let arrays: Vec<ArrayRef> = arrays
+            .iter()
+            .map(|array| arrow_cast::cast(array, &DataType::Float64).map_err(|e| DataLoaderError::GenericError(e.to_string())))
+            .collect::<Result<Vec<ArrayRef>, DataLoaderError>>()?;
+
+        Ok(Self::arrow_collect_values(&arrays, |array, i| array.as_primitive::<Float64Type>().value(i)))
+    }
+
+    // Reads timestamp, date and duration arrays as microseconds.
+    fn arrow_temporal_values(arrays: &[ArrayRef]) -> Result<Vec<Option<i64>>, DataLoaderError> {
+        let mut values: Vec<Option<i64>> = Vec::with_capacity(arrays.iter().map(|array| array.len()).sum());
+
+        for array in arrays {
+            let to_micros = |value: i64| -> Option<i64> {
+                match array.data_type() {
+                    DataType::Timestamp(TimeUnit::Second, _) | DataType::Duration(TimeUnit::Second) => value.checked_mul(1_000_000),
+                    DataType::Timestamp(TimeUnit::Millisecond, _) | DataType::Duration(TimeUnit::Millisecond) | DataType::Date64 => value.checked_mul(1_000),
+                    DataType::Timestamp(TimeUnit::Nanosecond, _) | DataType::Duration(TimeUnit::Nanosecond) => Some(value.div_euclid(1_000)),
+                    DataType::Date32 => value.checked_mul(86_400_000_000),
+                    _ => Some(value)
+                }
+            };
+
+            let raw: ArrayRef = arrow_cast::cast(array, &DataType::Int64).map_err(|e| DataLoaderError::GenericError(e.to_string()))?;
+            let raw: &Int64Array = raw.as_primitive::<Int64Type>();
+            values.extend((0..raw.len()).map(|i| if raw.is_null(i) { None } else { to_micros(raw.value(i)) }));
+        }
+
+        Ok(values)
+    }
+
+    pub(crate) fn arrow_string_values(arrays: &[ArrayRef]) -> Result<Vec<Option<String>>, DataLoaderError> {
+        let options: FormatOptions = FormatOptions::default();
+        let mut values: Vec<Option<String>> = Vec::new();
+        for array in arrays {
+            let formatter: ArrayFormatter = ArrayFormatter::try_new(array.as_ref(), &options)
+                .map_err(|e| DataLoaderError::GenericError(e.to_string()))?;
+            for i in 0..array.len() {
+                values.push(if array.is_null(i) { None } else { Some(formatter.value(i).to_string()) });
+            }
+        }
+        Ok(values)
+    }
+}
+
+impl Default for DataVector {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 impl std::fmt::Debug for DataVector {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "DataVector {{ label: {:?}, len: {} }}", self.label, self.len)
+        write!(f, "DataVector {{ label: {:?}, dtype: {}, len: {} }}", self.label, self.dtype(), self.len)
     }
 }
 
 impl std::fmt::Display for DataVector {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        Ok(if let Some(dtype) = self.dtype {
-            print!("{}: ", self.label.clone().unwrap_or(" ".to_string()));
-            match dtype {
-                "bool" => {
-                    let vec = self.data.downcast_ref::<Vec<bool>>().unwrap();
-                    writeln!(f, "{:?}", vec)?;
-                }
-                "i64" => {
-                    let vec = self.data.downcast_ref::<Vec<i64>>().unwrap();
-                    writeln!(f, "{:?}", vec)?;
-                }
-                "f64" => {
-                    let vec = self.data.downcast_ref::<Vec<f64>>().unwrap();
-                    writeln!(f, "{:?}", vec)?;
-                }
-                "alloc::string::String" | "String" => {
-                    let vec = self.data.downcast_ref::<Vec<String>>().unwrap();
-                    writeln!(f, "{:?}", vec)?;
-                }
-                _ => writeln!(f, "Unknown dtype '{}'", dtype)?
-            }
-        })
+        write!(f, "{}: ", self.label.clone().unwrap_or(" ".to_string()))?;
+        let values: Vec<String> = (0..self.data.len())
+            .map(|i| if self.is_null(i) { "null".to_string() } else { self.data.format_debug(i).unwrap_or_default() })
+            .collect();
+        writeln!(f, "[{}]", values.join(", "))
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn float16_arrays_are_read_as_f64() {
+        let source: ArrayRef = Arc::new(Float64Array::from(vec![Some(1.5), None, Some(-2.25)]));
+        let half: ArrayRef = arrow_cast::cast(&source, &DataType::Float16).unwrap();
+
+        let column: DataVector = DataVector::from_arrow_chunks(&DataType::Float16, &[half]).unwrap();
+        assert_eq!(column.dtype(), DType::F64);
+        assert_eq!(column.to_options::<f64>().unwrap(), vec![Some(1.5), None, Some(-2.25)]);
+    }
+
+    #[test]
+    fn to_categorical_with_keeps_the_given_categories_and_rejects_unseen_labels() {
+        let column: DataVector = DataVector::from_nullable(Some("pet".to_string()), vec![Some("dog".to_string()), None, Some("cat".to_string())]);
+        let categories: Vec<String> = vec!["dog".to_string(), "cat".to_string(), "fish".to_string()];
+
+        let encoded: DataVector = column.to_categorical_with(categories.clone()).unwrap();
+        assert_eq!(encoded.dtype(), DType::Categorical);
+        assert_eq!(encoded.categories(), Some(categories.as_slice()));
+        assert_eq!(encoded.to_options::<String>().unwrap(), column.to_options::<String>().unwrap());
+
+        assert!(column.to_categorical_with(vec!["dog".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,180 @@
+use std::{fs::File, io::{BufWriter, Write}};
+
+use async_trait::async_trait;
+use veracity_types::dtype::DType;
+
+use crate::{column_data::ColumnData, data_matrix::DataMatrix, data_vector::DataVector, enums::{error_types::DataLoaderError, quote_style::QuoteStyle}};
+
+use super::{data_writer_settings::DataWriterSettings, DataWriter};
+
+pub struct CSVWriter {
+    pub settings: CSVWriterSettings
+}
+
+#[derive(Clone)]
+pub struct CSVWriterSettings {
+    pub separator: char,
+    pub quote_char: char,
+    pub quote_style: QuoteStyle,
+    pub write_header: bool,
+    pub write_index: bool,
+    pub index_label: String,
+    pub float_precision: Option<usize>,
+    pub na_rep: String,
+    pub line_terminator: String
+}
+
+impl Default for CSVWriterSettings {
+    fn default() -> Self {
+        Self {
+            separator: ',',
+            quote_char: '"',
+            quote_style: QuoteStyle::Necessary,
+            write_header: true,
+            write_index: false,
+            index_label: String::new(),
+            float_precision: None,
+            na_rep: String::new(),
+            line_terminator: "\n".to_string()
+        }
+    }
+}
+
+impl DataWriterSettings for CSVWriterSettings {}
+
+impl CSVWriter {
+    pub fn new(settings: CSVWriterSettings) -> Self {
+        CSVWriter {
+            settings
+        }
+    }
+
+    pub fn write_to_writer<W: Write>(&self, matrix: &DataMatrix, mut writer: W) -> Result<(), DataLoaderError> {
+        let write_error = |e: std::io::Error| DataLoaderError::FileWrite(e.to_string());
+        let index: Vec<String> = matrix.index_labels();
+
+        if self.settings.write_header {
+            let mut fields: Vec<String> = Vec::with_capacity(matrix.columns.len() + 1);
+            if self.settings.write_index {
+                fields.push(self.quote(&self.settings.index_label, true));
+            }
+            fields.extend(matrix.columns.keys().map(|label| self.quote(label, true)));
+
+            self.write_record(&mut writer, &fields).map_err(write_error)?;
+        }
+
+        for (row, label) in index.iter().enumerate() {
+            let mut fields: Vec<String> = Vec::with_capacity(matrix.columns.len() + 1);
+            if self.settings.write_index {
+                fields.push(self.quote(label, true));
+            }
+            fields.extend(matrix.columns.values().map(|column| self.format_field(column, row)));
+
+            self.write_record(&mut writer, &fields).map_err(write_error)?;
+        }
+
+        writer.flush().map_err(write_error)
+    }
+
+    // A record made of one empty field is written as an empty quoted field, as a blank
+    // line would be skipped or read as no fields when loaded back.
+    fn write_record<W: Write>(&self, writer: &mut W, fields: &[String]) -> std::io::Result<()> {
+        if let [field] = fields && field.is_empty() {
+            write!(writer, "{}{}", self.settings.quote_char, self.settings.quote_char)?;
+            return writer.write_all(self.settings.line_terminator.as_bytes());
+        }
+
+        writer.write_all(fields.join(&self.settings.separator.to_string()).as_bytes())?;
+        writer.write_all(self.settings.line_terminator.as_bytes())
+    }
+
+    fn format_field(&self, column: &DataVector, row: usize) -> String {
+        if column.is_null(row) {
+            return self.settings.na_rep.clone();
+        }
+
+        let value: String = match (&column.data, self.settings.float_precision) {
+            (ColumnData::F32(vec), Some(precision)) => format!("{:.*}", precision, vec[row]),
+            (ColumnData::F64(vec), Some(precision)) => format!("{:.*}", precision, vec[row]),
+            (data, _) => data.display_value(row).unwrap_or_default()
+        };
+
+        self.quote(&value, !column.dtype().is_numeric() && column.dtype() != DType::Bool)
+    }
+
+    // Quotes a field according to the quote style, doubling any quote characters inside it.
+    // Necessary also quotes leading and trailing whitespace, which the loader would trim.
+    fn quote(&self, value: &str, is_text: bool) -> String {
+        let quote_char: char = self.settings.quote_char;
+
+        let needs_quotes: bool = match self.settings.quote_style {
+            QuoteStyle::Always => true,
+            QuoteStyle::NonNumeric => is_text,
+            QuoteStyle::Never => false,
+            QuoteStyle::Necessary => {
+                value.contains([self.settings.separator, quote_char, '\n', '\r']) || value.starts_with(char::is_whitespace) || value.ends_with(char::is_whitespace)
+            }
+        };
+
+        if needs_quotes {
+            let escaped: String = value.replace(quote_char, &format!("{}{}", quote_char, quote_char));
+            format!("{}{}{}", quote_char, escaped, quote_char)
+        } else {
+            value.to_string()
+        }
+    }
+}
+
+#[async_trait]
+impl DataWriter for CSVWriter {
+    async fn write_to<'a>(&'a self, matrix: &'a DataMatrix, path: &'a str) -> Result<(), DataLoaderError> {
+        let file: File = File::create(path).map_err(|e| DataLoaderError::FileWrite(e.to_string()))?;
+        self.write_to_writer(matrix, BufWriter::new(file))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data_loader::csv_loader::{CSVLoader, CSVLoaderSettings};
+
+    use super::*;
+
+    fn write(matrix: &DataMatrix) -> String {
+        let mut written: Vec<u8> = Vec::new();
+        CSVWriter::new(CSVWriterSettings::default()).write_to_writer(matrix, &mut written).unwrap();
+        String::from_utf8(written).unwrap()
+    }
+
+    #[test]
+    fn empty_records_and_padding_round_trip() {
+        let values: Vec<Option<String>> = vec![Some(" padded ".to_string()), Some(String::new()), Some("NA".to_string()), None];
+        let matrix: DataMatrix = DataMatrix::from_vec(vec![DataVector::from_nullable(Some("s".to_string()), values)]).unwrap();
+
+        let written: String = write(&matrix);
+        assert_eq!(written, "s\n\" padded \"\n\"\"\nNA\n\"\"\n");
+
+        let loaded: DataMatrix = CSVLoader::new(CSVLoaderSettings::default()).load_from_reader(written.as_bytes()).unwrap();
+        assert_eq!(loaded.get_column("s").unwrap().to_options::<String>().unwrap(), vec![Some(" padded ".to_string()), None, None, None]);
+
+        let verbatim: CSVLoaderSettings = CSVLoaderSettings { na_values: Vec::new(), ..Default::default() };
+        let loaded: DataMatrix = CSVLoader::new(verbatim).load_from_reader(written.as_bytes()).unwrap();
+        assert_eq!(
+            loaded.get_column("s").unwrap().to_options::<String>().unwrap(),
+            vec![Some(" padded ".to_string()), Some(String::new()), Some("NA".to_string()), Some(String::new())]
+        );
+    }
+
+    #[test]
+    fn separators_quotes_and_line_breaks_round_trip() {
+        let values: Vec<Option<String>> = vec![Some("a,b".to_string()), Some("say \"hi\"".to_string()), Some("two\nlines".to_string())];
+        let matrix: DataMatrix = DataMatrix::from_vec(vec![
+            DataVector::from_nullable(Some("text".to_string()), values.clone()),
+            DataVector::from_nullable(Some("n".to_string()), vec![Some(1i64), None, Some(3)])
+        ])
+        .unwrap();
+
+        let loaded: DataMatrix = CSVLoader::new(CSVLoaderSettings::default()).load_from_reader(write(&matrix).as_bytes()).unwrap();
+        assert_eq!(loaded.get_column("text").unwrap().to_options::<String>().unwrap(), values);
+        assert_eq!(loaded.get_column("n").unwrap().to_options::<i64>().unwrap(), vec![Some(1), None, Some(3)]);
+    }
+}
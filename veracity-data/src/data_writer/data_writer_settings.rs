@@ -0,0 +1 @@
+pub trait DataWriterSettings {}
\ No newline at end of file
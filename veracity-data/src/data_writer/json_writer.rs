@@ -0,0 +1,96 @@
+use std::{fs::File, io::{BufWriter, Write}};
+
+use async_trait::async_trait;
+use serde_json::{Number, Value};
+
+use crate::{column_data::ColumnData, data_loader::json_loader::JSONFormat, data_matrix::DataMatrix, data_vector::DataVector, enums::error_types::DataLoaderError};
+
+use super::{data_writer_settings::DataWriterSettings, DataWriter};
+
+pub struct JSONWriter {
+    pub settings: JSONWriterSettings
+}
+
+#[derive(Clone)]
+pub struct JSONWriterSettings {
+    pub format: JSONFormat,
+    pub write_index: bool,
+    pub index_label: String
+}
+
+impl Default for JSONWriterSettings {
+    fn default() -> Self {
+        Self {
+            format: JSONFormat::Records,
+            write_index: false,
+            index_label: "index".to_string()
+        }
+    }
+}
+
+impl DataWriterSettings for JSONWriterSettings {}
+
+impl JSONWriter {
+    pub fn new(settings: JSONWriterSettings) -> Self {
+        JSONWriter {
+            settings
+        }
+    }
+
+    // Writes one object per row with keys in column order. Auto is written as Records.
+    pub fn write_to_writer<W: Write>(&self, matrix: &DataMatrix, mut writer: W) -> Result<(), DataLoaderError> {
+        let write_error = |e: std::io::Error| DataLoaderError::FileWrite(e.to_string());
+        let index: Vec<String> = matrix.index_labels();
+
+        let keys: Vec<String> = matrix.columns.keys().map(|label| Value::String(label.clone()).to_string()).collect();
+        let index_key: String = Value::String(self.settings.index_label.clone()).to_string();
+
+        let records: Vec<String> = index
+            .iter()
+            .enumerate()
+            .map(|(row, label)| {
+                let mut fields: Vec<String> = Vec::with_capacity(keys.len() + 1);
+                if self.settings.write_index {
+                    fields.push(format!("{}:{}", index_key, Value::String(label.clone())));
+                }
+                for (key, column) in keys.iter().zip(matrix.columns.values()) {
+                    fields.push(format!("{}:{}", key, Self::json_value(column, row)));
+                }
+                format!("{{{}}}", fields.join(","))
+            })
+            .collect();
+
+        let contents: String = match self.settings.format {
+            JSONFormat::NDJSON => records.iter().map(|record| format!("{}\n", record)).collect(),
+            _ => format!("[{}]", records.join(","))
+        };
+
+        writer.write_all(contents.as_bytes()).map_err(write_error)?;
+        writer.flush().map_err(write_error)
+    }
+
+    // Nulls and non-finite floats become JSON null. Datetimes are written as text and
+    // durations as whole microseconds.
+    fn json_value(column: &DataVector, row: usize) -> Value {
+        if column.is_null(row) {
+            return Value::Null;
+        }
+
+        match &column.data {
+            ColumnData::Bool(vec) => Value::Bool(vec[row]),
+            ColumnData::I32(vec) => Value::from(vec[row]),
+            ColumnData::I64(vec) | ColumnData::Duration(vec) => Value::from(vec[row]),
+            ColumnData::F32(vec) => Number::from_f64(vec[row] as f64).map_or(Value::Null, Value::Number),
+            ColumnData::F64(vec) => Number::from_f64(vec[row]).map_or(Value::Null, Value::Number),
+            data => data.display_value(row).map_or(Value::Null, Value::String)
+        }
+    }
+}
+
+#[async_trait]
+impl DataWriter for JSONWriter {
+    async fn write_to<'a>(&'a self, matrix: &'a DataMatrix, path: &'a str) -> Result<(), DataLoaderError> {
+        let file: File = File::create(path).map_err(|e| DataLoaderError::FileWrite(e.to_string()))?;
+        self.write_to_writer(matrix, BufWriter::new(file))
+    }
+}
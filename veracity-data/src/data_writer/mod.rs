@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+
+use crate::{data_matrix::DataMatrix, enums::error_types::DataLoaderError};
+
+pub mod data_writer_settings;
+pub mod csv_writer;
+pub mod json_writer;
+
+
+#[async_trait]
+pub trait DataWriter {
+    async fn write_to<'a>(&'a self, matrix: &'a DataMatrix, path: &'a str) -> Result<(), DataLoaderError>;
+}
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use crate::data_vector::DataVector;
+
+// Custom aggregations receive the group's slice of the column, nulls included, and
+// return None to produce a null.
+pub type CustomAggregation = Arc<dyn Fn(&DataVector) -> Option<f64> + Send + Sync>;
+
+#[derive(Clone)]
+pub enum Aggregation {
+    Count,
+    Sum,
+    Mean,
+    Min,
+    Max,
+    Std,
+    Median,
+    First,
+    Last,
+    NUnique,
+    Custom(String, CustomAggregation)
+}
+
+impl Aggregation {
+    pub fn name(&self) -> &str {
+        match self {
+            Aggregation::Count => "count",
+            Aggregation::Sum => "sum",
+            Aggregation::Mean => "mean",
+            Aggregation::Min => "min",
+            Aggregation::Max => "max",
+            Aggregation::Std => "std",
+            Aggregation::Median => "median",
+            Aggregation::First => "first",
+            Aggregation::Last => "last",
+            Aggregation::NUnique => "n_unique",
+            Aggregation::Custom(name, _) => name
+        }
+    }
+
+    // Sum, Mean, Std and Median read values as f64, so they only apply to numeric and bool columns.
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, Aggregation::Sum | Aggregation::Mean | Aggregation::Std | Aggregation::Median)
+    }
+}
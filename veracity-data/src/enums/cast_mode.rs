@@ -0,0 +1,9 @@
+// Checked casts fail on any value that would change, such as 1.5 to an integer or an
+// out of range integer. Lossy casts truncate fractions, saturate out of range integers,
+// round to the nearest float and turn values that cannot be converted into nulls.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CastMode {
+    #[default]
+    Checked,
+    Lossy
+}
@@ -0,0 +1,7 @@
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CorrelationMethod {
+    #[default]
+    Pearson,
+    Spearman,
+    Kendall
+}
@@ -14,7 +14,12 @@ pub enum DataLoaderError {
     HeterogeneousColumnLengths,
     NoData,
     IndexError(usize),
+    NullValues(String),
+    ColumnMismatch { missing: Vec<String>, unexpected: Vec<String> },
     FileRead(String),
+    FileWrite(String),
+    ParseError(String),
+    CastError(String),
     GenericError(String)
 }
 
@@ -31,7 +36,12 @@ impl fmt::Display for DataLoaderError {
             DataLoaderError::HeterogeneousColumnLengths => write!(f, "All columns in a DataMatrix must be the same length."),
             DataLoaderError::NoData => write!(f, "The DataMatrix contains no data"),
             DataLoaderError::IndexError(index) => write!(f, "No element was found at index: {}", index),
+            DataLoaderError::NullValues(label) => write!(f, "Column '{}' contains null values; use drop_nulls or fill_null first.", label),
+            DataLoaderError::ColumnMismatch { missing, unexpected } => write!(f, "DataMatrix columns do not match the expected columns; missing: [{}], unexpected: [{}]", missing.join(", "), unexpected.join(", ")),
             DataLoaderError::FileRead(e) => write!(f, "An error occurred reading from file:\r\n{:#?}", e),
+            DataLoaderError::FileWrite(e) => write!(f, "An error occurred writing to file:\r\n{:#?}", e),
+            DataLoaderError::ParseError(e) => write!(f, "An error occurred parsing file contents:\r\n{:#?}", e),
+            DataLoaderError::CastError(e) => write!(f, "An error occurred casting a column:\r\n{:#?}", e),
             DataLoaderError::GenericError(e) => write!(f, "An error occurred in DataLoader:\r\n{:#?}", e)
         }
     }
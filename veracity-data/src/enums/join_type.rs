@@ -0,0 +1,7 @@
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JoinType {
+    Inner,
+    Left,
+    Right,
+    Outer
+}
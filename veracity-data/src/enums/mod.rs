@@ -1 +1,9 @@
-pub mod error_types;
\ No newline at end of file
+pub mod aggregation;
+pub mod cast_mode;
+pub mod correlation_method;
+pub mod error_types;
+pub mod join_type;
+pub mod null_placement;
+pub mod quote_style;
+pub mod sample_size;
+pub mod schema_violation;
\ No newline at end of file
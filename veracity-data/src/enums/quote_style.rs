@@ -0,0 +1,8 @@
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum QuoteStyle {
+    #[default]
+    Necessary,
+    Always,
+    NonNumeric,
+    Never
+}
@@ -0,0 +1,5 @@
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SampleSize {
+    N(usize),
+    Frac(f64)
+}
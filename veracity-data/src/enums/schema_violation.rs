@@ -0,0 +1,27 @@
+use std::fmt;
+
+use veracity_types::dtype::DType;
+
+// Rows are identified by their index label.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SchemaViolation {
+    MissingColumn(String),
+    UnexpectedColumn(String),
+    WrongDType { column: String, expected: DType, actual: DType },
+    NullValue { column: String, row: String },
+    OutOfRange { column: String, row: String, value: f64 },
+    UnknownValue { column: String, row: String, value: String }
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaViolation::MissingColumn(column) => write!(f, "Column '{}' is missing", column),
+            SchemaViolation::UnexpectedColumn(column) => write!(f, "Column '{}' is not in the schema", column),
+            SchemaViolation::WrongDType { column, expected, actual } => write!(f, "Column '{}' is {} but the schema expects {}", column, actual, expected),
+            SchemaViolation::NullValue { column, row } => write!(f, "Column '{}' is null in row '{}' but is not nullable", column, row),
+            SchemaViolation::OutOfRange { column, row, value } => write!(f, "Column '{}' has {} in row '{}', outside the allowed range", column, value, row),
+            SchemaViolation::UnknownValue { column, row, value } => write!(f, "Column '{}' has '{}' in row '{}', which is not an allowed value", column, value, row)
+        }
+    }
+}
@@ -0,0 +1,298 @@
+use std::collections::{HashMap, HashSet};
+
+use indexmap::IndexMap;
+use veracity_types::dtype::DType;
+
+use crate::{data_matrix::DataMatrix, data_vector::DataVector, enums::{aggregation::Aggregation, error_types::DataLoaderError}};
+
+fn is_numeric(column: &DataVector) -> bool {
+    column.dtype().is_numeric() || column.dtype() == DType::Bool
+}
+
+pub struct GroupBy<'a> {
+    matrix: &'a DataMatrix,
+    keys: Vec<String>,
+    groups: Vec<Vec<usize>>
+}
+
+impl<'a> GroupBy<'a> {
+    // Groups are kept in the order their first row appears. Null keys form a group of
+    // their own rather than being dropped.
+    pub(crate) fn new(matrix: &'a DataMatrix, keys: Vec<&str>) -> Result<Self, DataLoaderError> {
+        if keys.is_empty() {
+            return Err(DataLoaderError::GenericError("group_by needs at least one key column".to_string()));
+        }
+
+        let key_columns: Vec<&DataVector> = keys
+            .iter()
+            .map(|&name| matrix.columns.get(name).ok_or(DataLoaderError::GenericError(format!("Column '{}' does not exist", name))))
+            .collect::<Result<Vec<&DataVector>, DataLoaderError>>()?;
+
+        let mut positions: HashMap<Vec<Option<String>>, usize> = HashMap::new();
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+
+        for row in 0..matrix.num_rows() {
+            let key: Vec<Option<String>> = key_columns
+                .iter()
+                .map(|column| if column.is_null(row) { None } else { column.data.format_debug(row) })
+                .collect();
+
+            let group: usize = *positions.entry(key).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[group].push(row);
+        }
+
+        Ok(GroupBy {
+            matrix,
+            keys: keys.iter().map(|key| key.to_string()).collect(),
+            groups
+        })
+    }
+
+    pub fn n_groups(&self) -> usize {
+        self.groups.len()
+    }
+
+    // One row per group: the key columns followed by a "{column}_{aggregation}" column
+    // for every requested aggregation.
+    pub fn agg(&self, aggregations: Vec<(&str, Aggregation)>) -> Result<DataMatrix, DataLoaderError> {
+        let mut columns: IndexMap<String, DataVector> = self.key_columns()?;
+
+        for (name, aggregation) in aggregations {
+            let column: &DataVector = self.matrix.columns
+                .get(name)
+                .ok_or(DataLoaderError::GenericError(format!("Column '{}' does not exist", name)))?;
+
+            let label: String = format!("{}_{}", name, aggregation.name());
+            if columns.contains_key(&label) {
+                return Err(DataLoaderError::DuplicateLabel);
+            }
+
+            columns.insert(label.clone(), self.aggregate(column, &aggregation, label)?);
+        }
+
+        Ok(self.build(columns))
+    }
+
+    // Applies one aggregation to every non-key column, keeping the column names. Numeric
+    // aggregations skip columns that are not numeric or bool.
+    pub fn agg_all(&self, aggregation: Aggregation) -> Result<DataMatrix, DataLoaderError> {
+        let mut columns: IndexMap<String, DataVector> = self.key_columns()?;
+
+        for (label, column) in self.matrix.columns.iter() {
+            if self.keys.contains(label) || (aggregation.is_numeric() && !is_numeric(column)) {
+                continue;
+            }
+            columns.insert(label.clone(), self.aggregate(column, &aggregation, label.clone())?);
+        }
+
+        Ok(self.build(columns))
+    }
+
+    fn key_columns(&self) -> Result<IndexMap<String, DataVector>, DataLoaderError> {
+        let first_rows: Vec<usize> = self.groups.iter().map(|group| group[0]).collect();
+
+        self.keys
+            .iter()
+            .map(|key| Ok((key.clone(), self.matrix.columns[key].take(&first_rows)?)))
+            .collect()
+    }
+
+    fn build(&self, columns: IndexMap<String, DataVector>) -> DataMatrix {
+        DataMatrix {
+            columns,
+            index: (0..self.groups.len()).map(|i| format!("{}", i)).collect()
+        }
+    }
+
+    fn aggregate(&self, column: &DataVector, aggregation: &Aggregation, label: String) -> Result<DataVector, DataLoaderError> {
+        let valid_rows = |group: &'_ Vec<usize>| group.iter().copied().filter(|&row| !column.is_null(row)).collect::<Vec<usize>>();
+
+        let result: DataVector = match aggregation {
+            Aggregation::Count => {
+                let counts: Vec<Option<i64>> = self.groups.iter().map(|group| Some(valid_rows(group).len() as i64)).collect();
+                DataVector::from_nullable(Some(label), counts)
+            },
+            Aggregation::NUnique => {
+                let counts: Vec<Option<i64>> = self.groups
+                    .iter()
+                    .map(|group| Some(valid_rows(group).into_iter().filter_map(|row| column.data.format_debug(row)).collect::<HashSet<String>>().len() as i64))
+                    .collect();
+                DataVector::from_nullable(Some(label), counts)
+            },
+            Aggregation::Sum if matches!(column.dtype(), DType::Bool | DType::I32 | DType::I64) => {
+                let sums: Vec<Option<i64>> = self.groups
+                    .iter()
+                    .map(|group| {
+                        valid_rows(group)
+                            .into_iter()
+                            .filter_map(|row| column.data.value_as_i64(row))
+                            .try_fold(0i64, i64::checked_add)
+                            .ok_or(DataLoaderError::GenericError(format!("Sum of column '{}' overflowed i64", label)))
+                            .map(Some)
+                    })
+                    .collect::<Result<Vec<Option<i64>>, DataLoaderError>>()?;
+                DataVector::from_nullable(Some(label), sums)
+            },
+            Aggregation::Sum => self.numeric(column, label, |values| Some(values.iter().sum()))?,
+            Aggregation::Mean => self.numeric(column, label, |values| {
+                if values.is_empty() { None } else { Some(values.iter().sum::<f64>() / values.len() as f64) }
+            })?,
+            Aggregation::Std => self.numeric(column, label, |values| {
+                if values.len() < 2 {
+                    return None;
+                }
+                let mean: f64 = values.iter().sum::<f64>() / values.len() as f64;
+                let variance: f64 = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64;
+                Some(variance.sqrt())
+            })?,
+            Aggregation::Median => self.numeric(column, label, |values| {
+                if values.is_empty() {
+                    return None;
+                }
+                let mut sorted: Vec<f64> = values.to_vec();
+                sorted.sort_by(f64::total_cmp);
+                let mid: usize = sorted.len() / 2;
+                Some(if sorted.len().is_multiple_of(2) { (sorted[mid - 1] + sorted[mid]) / 2.0 } else { sorted[mid] })
+            })?,
+            Aggregation::Min => self.select(column, label, |rows| rows.iter().copied().min_by(|&a, &b| column.data.compare_rows(a, b)))?,
+            Aggregation::Max => self.select(column, label, |rows| rows.iter().copied().max_by(|&a, &b| column.data.compare_rows(a, b)))?,
+            Aggregation::First => self.select(column, label, |rows| rows.first().copied())?,
+            Aggregation::Last => self.select(column, label, |rows| rows.last().copied())?,
+            Aggregation::Custom(_, function) => {
+                let values: Vec<Option<f64>> = self.groups
+                    .iter()
+                    .map(|group| Ok(function(&column.take(group)?)))
+                    .collect::<Result<Vec<Option<f64>>, DataLoaderError>>()?;
+                DataVector::from_nullable(Some(label), values)
+            }
+        };
+
+        Ok(result)
+    }
+
+    // Runs reduce over each group's non-null values as f64.
+    fn numeric(&self, column: &DataVector, label: String, reduce: impl Fn(&[f64]) -> Option<f64>) -> Result<DataVector, DataLoaderError> {
+        if !is_numeric(column) {
+            return Err(DataLoaderError::GenericError(format!("Column '{}' is not numeric", column.label.as_deref().unwrap_or_default())));
+        }
+
+        let values: Vec<Option<f64>> = self.groups
+            .iter()
+            .map(|group| {
+                let values: Vec<f64> = group
+                    .iter()
+                    .filter(|&&row| !column.is_null(row))
+                    .filter_map(|&row| column.data.value_as_f64(row))
+                    .collect();
+                reduce(&values)
+            })
+            .collect();
+
+        Ok(DataVector::from_nullable(Some(label), values))
+    }
+
+    // Picks one row per group from its non-null rows, keeping the column's dtype. Groups
+    // with nothing to pick become nulls.
+    fn select(&self, column: &DataVector, label: String, pick: impl Fn(&[usize]) -> Option<usize>) -> Result<DataVector, DataLoaderError> {
+        let picked: Vec<Option<usize>> = self.groups
+            .iter()
+            .map(|group| pick(&group.iter().copied().filter(|&row| !column.is_null(row)).collect::<Vec<usize>>()))
+            .collect();
+
+        let rows: Vec<usize> = picked.iter().zip(self.groups.iter()).map(|(row, group)| row.unwrap_or(group[0])).collect();
+
+        let mut selected: DataVector = column.take(&rows)?;
+        selected.label = Some(label);
+        if picked.contains(&None) {
+            selected.validity = Some(picked.iter().map(Option::is_some).collect());
+        }
+
+        Ok(selected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[Option<&str>]) -> Vec<Option<String>> {
+        values.iter().map(|value| value.map(str::to_string)).collect()
+    }
+
+    fn matrix() -> DataMatrix {
+        DataMatrix::from_vec(vec![
+            DataVector::from_nullable(Some("g".to_string()), strings(&[Some("a"), Some("b"), Some("a"), Some("a"), Some("b"), Some("a"), None])),
+            DataVector::from_nullable(Some("f".to_string()), vec![Some(1.0), Some(2.0), Some(3.0), None, Some(4.0), Some(8.0), Some(7.0)]),
+            DataVector::from_nullable(Some("s".to_string()), strings(&[None, Some("y"), Some("c"), Some("a"), None, None, None]))
+        ])
+        .unwrap()
+    }
+
+    fn floats(matrix: &DataMatrix, name: &str) -> Vec<Option<f64>> {
+        matrix.get_column(name).unwrap().to_options::<f64>().unwrap()
+    }
+
+    #[test]
+    fn groups_keep_first_appearance_order_with_a_null_group() {
+        let matrix: DataMatrix = matrix();
+        let grouped: DataMatrix = matrix.group_by(vec!["g"]).unwrap().agg(vec![("f", Aggregation::Count)]).unwrap();
+
+        assert_eq!(grouped.get_column("g").unwrap().to_options::<String>().unwrap(), strings(&[Some("a"), Some("b"), None]));
+        assert_eq!(grouped.get_column("f_count").unwrap().to_options::<i64>().unwrap(), vec![Some(3), Some(2), Some(1)]);
+    }
+
+    #[test]
+    fn std_is_the_sample_deviation_and_median_averages_the_middle() {
+        let matrix: DataMatrix = matrix();
+        let grouped: DataMatrix = matrix.group_by(vec!["g"]).unwrap().agg(vec![("f", Aggregation::Std), ("f", Aggregation::Median)]).unwrap();
+
+        assert_eq!(floats(&grouped, "f_std"), vec![Some(13f64.sqrt()), Some(2f64.sqrt()), None]);
+        assert_eq!(floats(&grouped, "f_median"), vec![Some(3.0), Some(3.0), Some(7.0)]);
+    }
+
+    #[test]
+    fn selections_skip_nulls() {
+        let matrix: DataMatrix = matrix();
+        let grouped: DataMatrix = matrix
+            .group_by(vec!["g"])
+            .unwrap()
+            .agg(vec![("s", Aggregation::Min), ("s", Aggregation::Max), ("s", Aggregation::First), ("s", Aggregation::Last)])
+            .unwrap();
+
+        assert_eq!(grouped.get_column("s_min").unwrap().to_options::<String>().unwrap(), strings(&[Some("a"), Some("y"), None]));
+        assert_eq!(grouped.get_column("s_max").unwrap().to_options::<String>().unwrap(), strings(&[Some("c"), Some("y"), None]));
+        assert_eq!(grouped.get_column("s_first").unwrap().to_options::<String>().unwrap(), strings(&[Some("c"), Some("y"), None]));
+        assert_eq!(grouped.get_column("s_last").unwrap().to_options::<String>().unwrap(), strings(&[Some("a"), Some("y"), None]));
+    }
+
+    #[test]
+    fn integer_sums_are_checked() {
+        let matrix: DataMatrix = DataMatrix::from_vec(vec![
+            DataVector::from_nullable(Some("g".to_string()), vec![Some(1i64), Some(1), Some(2)]),
+            DataVector::from_nullable(Some("x".to_string()), vec![Some(i64::MAX), Some(1), Some(i64::MAX)])
+        ])
+        .unwrap();
+
+        assert!(matches!(matrix.group_by(vec!["g"]).unwrap().agg(vec![("x", Aggregation::Sum)]), Err(DataLoaderError::GenericError(_))));
+
+        let within_range: DataMatrix = matrix.iloc(1..).unwrap();
+        let grouped: DataMatrix = within_range.group_by(vec!["g"]).unwrap().agg(vec![("x", Aggregation::Sum)]).unwrap();
+        assert_eq!(grouped.get_column("x_sum").unwrap().to_options::<i64>().unwrap(), vec![Some(1), Some(i64::MAX)]);
+    }
+
+    #[test]
+    fn agg_all_skips_non_numeric_columns_for_numeric_aggregations() {
+        let matrix: DataMatrix = matrix();
+        let group_by: GroupBy = matrix.group_by(vec!["g"]).unwrap();
+
+        let means: DataMatrix = group_by.agg_all(Aggregation::Mean).unwrap();
+        assert_eq!(means.column_names(), vec!["g", "f"]);
+        assert_eq!(floats(&means, "f"), vec![Some(4.0), Some(3.0), Some(7.0)]);
+
+        assert_eq!(group_by.agg_all(Aggregation::First).unwrap().column_names(), vec!["g", "f", "s"]);
+        assert!(group_by.agg(vec![("s", Aggregation::Mean)]).is_err());
+    }
+}
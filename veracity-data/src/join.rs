@@ -0,0 +1,352 @@
+use std::{collections::{HashMap, HashSet}, hash::Hash};
+
+use indexmap::IndexMap;
+use veracity_types::dtype::DType;
+
+use crate::{data_matrix::DataMatrix, data_vector::DataVector, enums::{error_types::DataLoaderError, join_type::JoinType}};
+
+const LEFT_SUFFIX: &str = "_left";
+const RIGHT_SUFFIX: &str = "_right";
+
+type RowPairs = Vec<(Option<usize>, Option<usize>)>;
+
+// Pairs up left and right rows with equal keys; None keys never match. Inner and left
+// joins follow the left row order, right joins the right row order, and outer joins
+// append the unmatched right rows after the left ones.
+fn match_rows<K: Hash + Eq>(left_keys: &[Option<K>], right_keys: &[Option<K>], how: JoinType) -> RowPairs {
+    if how == JoinType::Right {
+        return match_rows(right_keys, left_keys, JoinType::Left)
+            .into_iter()
+            .map(|(right_row, left_row)| (left_row, right_row))
+            .collect();
+    }
+
+    let mut right_rows: HashMap<&K, Vec<usize>> = HashMap::new();
+    for (row, key) in right_keys.iter().enumerate() {
+        if let Some(key) = key {
+            right_rows.entry(key).or_default().push(row);
+        }
+    }
+
+    let mut pairs: RowPairs = Vec::new();
+    let mut matched: Vec<bool> = vec![false; right_keys.len()];
+
+    for (left_row, key) in left_keys.iter().enumerate() {
+        match key.as_ref().and_then(|key| right_rows.get(key)) {
+            Some(rows) => {
+                for &right_row in rows {
+                    matched[right_row] = true;
+                    pairs.push((Some(left_row), Some(right_row)));
+                }
+            },
+            None if how != JoinType::Inner => pairs.push((Some(left_row), None)),
+            None => {}
+        }
+    }
+
+    if how == JoinType::Outer {
+        pairs.extend(matched.iter().enumerate().filter(|&(_, &matched)| !matched).map(|(row, _)| (None, Some(row))));
+    }
+
+    pairs
+}
+
+// Widest dtype both columns can be stored as when stacking rows.
+pub(crate) fn common_dtype(a: DType, b: DType) -> Option<DType> {
+    if a == b {
+        Some(a)
+    } else if a.is_integer() && b.is_integer() {
+        Some(DType::I64)
+    } else if a.is_numeric() && b.is_numeric() {
+        Some(DType::F64)
+    } else {
+        None
+    }
+}
+
+pub(crate) fn promote(column: &DataVector, dtype: DType) -> DataVector {
+    let rows = 0..column.len;
+
+    match dtype {
+        dtype if dtype == column.dtype() => column.clone(),
+        DType::I64 => DataVector::from_nullable(column.label.clone(), rows.map(|row| if column.is_null(row) { None } else { column.data.value_as_i64(row) }).collect()),
+        _ => DataVector::from_nullable(column.label.clone(), rows.map(|row| if column.is_null(row) { None } else { column.data.value_as_f64(row) }).collect())
+    }
+}
+
+pub(crate) fn insert_column(columns: &mut IndexMap<String, DataVector>, label: String, mut column: DataVector) -> Result<(), DataLoaderError> {
+    if columns.contains_key(&label) {
+        return Err(DataLoaderError::DuplicateLabel);
+    }
+
+    column.add_label(&label);
+    columns.insert(label, column);
+    Ok(())
+}
+
+impl DataMatrix {
+    // Joins rows with equal values in the on columns, which must exist on both sides with
+    // the same dtype. Rows with a null key never match. Other columns present on both
+    // sides are suffixed with _left and _right, and the result gets a fresh index.
+    pub fn join(&self, other: &DataMatrix, on: Vec<&str>, how: JoinType) -> Result<DataMatrix, DataLoaderError> {
+        if on.is_empty() {
+            return Err(DataLoaderError::GenericError("join needs at least one key column".to_string()));
+        }
+
+        let mut left_keys: Vec<&DataVector> = Vec::with_capacity(on.len());
+        let mut right_keys: Vec<&DataVector> = Vec::with_capacity(on.len());
+
+        for &name in on.iter() {
+            let left: &DataVector = self.columns.get(name).ok_or(DataLoaderError::GenericError(format!("Join column '{}' does not exist in the left matrix", name)))?;
+            let right: &DataVector = other.columns.get(name).ok_or(DataLoaderError::GenericError(format!("Join column '{}' does not exist in the right matrix", name)))?;
+
+            if left.dtype() != right.dtype() {
+                return Err(DataLoaderError::GenericError(format!("Join column '{}' is {} on the left and {} on the right", name, left.dtype(), right.dtype())));
+            }
+
+            left_keys.push(left);
+            right_keys.push(right);
+        }
+
+        let row_keys = |keys: &[&DataVector], num_rows: usize| -> Vec<Option<Vec<String>>> {
+            (0..num_rows)
+                .map(|row| keys.iter().map(|column| if column.is_null(row) { None } else { column.data.format_debug(row) }).collect())
+                .collect()
+        };
+
+        let pairs: RowPairs = match_rows(&row_keys(&left_keys, self.num_rows()), &row_keys(&right_keys, other.num_rows()), how);
+        let left_rows: Vec<Option<usize>> = pairs.iter().map(|&(left_row, _)| left_row).collect();
+        let right_rows: Vec<Option<usize>> = pairs.iter().map(|&(_, right_row)| right_row).collect();
+
+        let mut columns: IndexMap<String, DataVector> = IndexMap::new();
+
+        for (label, column) in self.columns.iter() {
+            if on.contains(&label.as_str()) {
+                // Key values come from whichever side the row was found on.
+                let mut combined: DataVector = column.clone();
+                combined.append(&other.columns[label])?;
+
+                let rows: Vec<usize> = pairs
+                    .iter()
+                    .map(|&(left_row, right_row)| left_row.or(right_row.map(|row| column.len + row)).ok_or(DataLoaderError::NoData))
+                    .collect::<Result<Vec<usize>, DataLoaderError>>()?;
+
+                insert_column(&mut columns, label.clone(), combined.take(&rows)?)?;
+            } else {
+                let output_label: String = if other.columns.contains_key(label) { format!("{}{}", label, LEFT_SUFFIX) } else { label.clone() };
+                insert_column(&mut columns, output_label, column.take_optional(&left_rows)?)?;
+            }
+        }
+
+        for (label, column) in other.columns.iter() {
+            if on.contains(&label.as_str()) {
+                continue;
+            }
+
+            let output_label: String = if self.columns.contains_key(label) { format!("{}{}", label, RIGHT_SUFFIX) } else { label.clone() };
+            insert_column(&mut columns, output_label, column.take_optional(&right_rows)?)?;
+        }
+
+        Ok(DataMatrix {
+            columns,
+            index: (0..pairs.len()).map(|i| format!("{}", i)).collect()
+        })
+    }
+
+    // Joins rows with equal index labels. The result keeps the matched labels as its index.
+    pub fn join_on_index(&self, other: &DataMatrix, how: JoinType) -> Result<DataMatrix, DataLoaderError> {
+        self.join_index(other, how, true)
+    }
+
+    // Stacks the rows of every matrix. Columns are matched by name; a column missing
+    // from one matrix is filled with nulls for its rows, and integer or float columns
+    // of different widths are widened to i64 or f64. Unless ignore_index is set, the
+    // combined index labels must be unique.
+    pub fn concat(matrices: Vec<&DataMatrix>, ignore_index: bool) -> Result<DataMatrix, DataLoaderError> {
+        let mut dtypes: IndexMap<String, DType> = IndexMap::new();
+
+        for matrix in matrices.iter() {
+            for (label, column) in matrix.columns.iter() {
+                let dtype: DType = match dtypes.get(label) {
+                    Some(&existing) => common_dtype(existing, column.dtype()).ok_or(DataLoaderError::GenericError(format!("Column '{}' is {} in one matrix and {} in another", label, existing, column.dtype())))?,
+                    None => column.dtype()
+                };
+                dtypes.insert(label.clone(), dtype);
+            }
+        }
+
+        let mut columns: IndexMap<String, DataVector> = IndexMap::new();
+
+        for (label, &dtype) in dtypes.iter() {
+            let mut stacked: DataVector = DataVector::nulls(Some(label.clone()), dtype, 0);
+
+            for matrix in matrices.iter() {
+                let part: DataVector = match matrix.columns.get(label) {
+                    Some(column) => promote(column, dtype),
+                    None => DataVector::nulls(None, dtype, matrix.num_rows())
+                };
+                stacked.append(&part)?;
+            }
+
+            columns.insert(label.clone(), stacked);
+        }
+
+        let num_rows: usize = matrices.iter().map(|matrix| matrix.num_rows()).sum();
+
+        let index: Vec<String> = if ignore_index {
+            (0..num_rows).map(|i| format!("{}", i)).collect()
+        } else {
+            let index: Vec<String> = matrices.iter().flat_map(|matrix| matrix.index_labels()).collect();
+            if index.iter().collect::<HashSet<&String>>().len() != index.len() {
+                return Err(DataLoaderError::DuplicateIndex);
+            }
+            index
+        };
+
+        Ok(DataMatrix { columns, index })
+    }
+
+    // Places the columns of every matrix side by side, aligning rows on the index.
+    // Column names must be unique across the matrices.
+    pub fn concat_columns(matrices: Vec<&DataMatrix>, how: JoinType) -> Result<DataMatrix, DataLoaderError> {
+        let mut matrices = matrices.into_iter();
+
+        let mut combined: DataMatrix = match matrices.next() {
+            Some(first) => first.clone(),
+            None => return Ok(DataMatrix::new())
+        };
+
+        for matrix in matrices {
+            combined = combined.join_index(matrix, how, false)?;
+        }
+
+        Ok(combined)
+    }
+
+    fn join_index(&self, other: &DataMatrix, how: JoinType, suffix_overlaps: bool) -> Result<DataMatrix, DataLoaderError> {
+        let left_index: Vec<String> = self.index_labels();
+        let right_index: Vec<String> = other.index_labels();
+
+        let left_keys: Vec<Option<&String>> = left_index.iter().map(Some).collect();
+        let right_keys: Vec<Option<&String>> = right_index.iter().map(Some).collect();
+
+        let pairs: RowPairs = match_rows(&left_keys, &right_keys, how);
+        let left_rows: Vec<Option<usize>> = pairs.iter().map(|&(left_row, _)| left_row).collect();
+        let right_rows: Vec<Option<usize>> = pairs.iter().map(|&(_, right_row)| right_row).collect();
+
+        let mut columns: IndexMap<String, DataVector> = IndexMap::new();
+
+        for (label, column) in self.columns.iter() {
+            let output_label: String = if suffix_overlaps && other.columns.contains_key(label) { format!("{}{}", label, LEFT_SUFFIX) } else { label.clone() };
+            insert_column(&mut columns, output_label, column.take_optional(&left_rows)?)?;
+        }
+
+        for (label, column) in other.columns.iter() {
+            let output_label: String = if suffix_overlaps && self.columns.contains_key(label) { format!("{}{}", label, RIGHT_SUFFIX) } else { label.clone() };
+            insert_column(&mut columns, output_label, column.take_optional(&right_rows)?)?;
+        }
+
+        let index: Vec<String> = pairs
+            .iter()
+            .map(|&(left_row, right_row)| match (left_row, right_row) {
+                (Some(row), _) => left_index[row].clone(),
+                (None, Some(row)) => right_index[row].clone(),
+                (None, None) => String::new()
+            })
+            .collect();
+
+        Ok(DataMatrix { columns, index })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[Option<&str>]) -> Vec<Option<String>> {
+        values.iter().map(|value| value.map(str::to_string)).collect()
+    }
+
+    fn left() -> DataMatrix {
+        DataMatrix::from_vec(vec![
+            DataVector::from_nullable(Some("id".to_string()), vec![Some(1i64), Some(2), None, Some(3)]),
+            DataVector::from_nullable(Some("lv".to_string()), strings(&[Some("a"), Some("b"), Some("c"), Some("d")])),
+            DataVector::from_nullable(Some("v".to_string()), vec![Some(10i32), Some(20), Some(30), Some(40)])
+        ])
+        .unwrap()
+    }
+
+    fn right() -> DataMatrix {
+        DataMatrix::from_vec(vec![
+            DataVector::from_nullable(Some("id".to_string()), vec![Some(3i64), Some(1), None, Some(1), Some(4)]),
+            DataVector::from_nullable(Some("v".to_string()), vec![Some(0i32), Some(1), Some(2), Some(3), Some(4)]),
+            DataVector::from_nullable(Some("rv".to_string()), strings(&[Some("r0"), Some("r1"), Some("r2"), Some("r3"), Some("r4")]))
+        ])
+        .unwrap()
+    }
+
+    fn join(how: JoinType) -> DataMatrix {
+        left().join(&right(), vec!["id"], how).unwrap()
+    }
+
+    #[test]
+    fn inner_join_follows_left_order_and_suffixes_overlaps() {
+        let joined: DataMatrix = join(JoinType::Inner);
+
+        assert_eq!(joined.column_names(), vec!["id", "lv", "v_left", "v_right", "rv"]);
+        assert_eq!(joined.index, vec!["0", "1", "2"]);
+        assert_eq!(joined.get_column("id").unwrap().to_options::<i64>().unwrap(), vec![Some(1), Some(1), Some(3)]);
+        assert_eq!(joined.get_column("v_left").unwrap().to_options::<i32>().unwrap(), vec![Some(10), Some(10), Some(40)]);
+        assert_eq!(joined.get_column("v_right").unwrap().to_options::<i32>().unwrap(), vec![Some(1), Some(3), Some(0)]);
+        assert_eq!(joined.get_column("rv").unwrap().to_options::<String>().unwrap(), strings(&[Some("r1"), Some("r3"), Some("r0")]));
+    }
+
+    #[test]
+    fn left_join_keeps_unmatched_left_rows_and_null_keys() {
+        let joined: DataMatrix = join(JoinType::Left);
+
+        assert_eq!(joined.get_column("id").unwrap().to_options::<i64>().unwrap(), vec![Some(1), Some(1), Some(2), None, Some(3)]);
+        assert_eq!(joined.get_column("lv").unwrap().to_options::<String>().unwrap(), strings(&[Some("a"), Some("a"), Some("b"), Some("c"), Some("d")]));
+        assert_eq!(joined.get_column("rv").unwrap().to_options::<String>().unwrap(), strings(&[Some("r1"), Some("r3"), None, None, Some("r0")]));
+    }
+
+    #[test]
+    fn right_join_follows_right_order() {
+        let joined: DataMatrix = join(JoinType::Right);
+
+        assert_eq!(joined.get_column("id").unwrap().to_options::<i64>().unwrap(), vec![Some(3), Some(1), None, Some(1), Some(4)]);
+        assert_eq!(joined.get_column("lv").unwrap().to_options::<String>().unwrap(), strings(&[Some("d"), Some("a"), None, Some("a"), None]));
+        assert_eq!(joined.get_column("v_right").unwrap().to_options::<i32>().unwrap(), vec![Some(0), Some(1), Some(2), Some(3), Some(4)]);
+    }
+
+    #[test]
+    fn outer_join_appends_unmatched_right_rows() {
+        let joined: DataMatrix = join(JoinType::Outer);
+
+        assert_eq!(joined.get_column("id").unwrap().to_options::<i64>().unwrap(), vec![Some(1), Some(1), Some(2), None, Some(3), None, Some(4)]);
+        assert_eq!(joined.get_column("lv").unwrap().to_options::<String>().unwrap(), strings(&[Some("a"), Some("a"), Some("b"), Some("c"), Some("d"), None, None]));
+        assert_eq!(joined.get_column("rv").unwrap().to_options::<String>().unwrap(), strings(&[Some("r1"), Some("r3"), None, None, Some("r0"), Some("r2"), Some("r4")]));
+    }
+
+    #[test]
+    fn null_keys_never_match() {
+        let nulls = |len: usize| DataMatrix::from_vec(vec![DataVector::from_nullable(Some("id".to_string()), vec![None::<i64>; len])]).unwrap();
+
+        assert_eq!(nulls(2).join(&nulls(3), vec!["id"], JoinType::Inner).unwrap().num_rows(), 0);
+        assert_eq!(nulls(2).join(&nulls(3), vec!["id"], JoinType::Outer).unwrap().num_rows(), 5);
+    }
+
+    #[test]
+    fn concat_rejects_duplicate_index_labels() {
+        let mut first: DataMatrix = left();
+        first.set_index(vec!["a", "b", "c", "d"]).unwrap();
+        let mut second: DataMatrix = left();
+        second.set_index(vec!["e", "f", "a", "g"]).unwrap();
+
+        assert!(matches!(DataMatrix::concat(vec![&first, &second], false), Err(DataLoaderError::DuplicateIndex)));
+
+        let stacked: DataMatrix = DataMatrix::concat(vec![&first, &second], true).unwrap();
+        assert_eq!(stacked.index, (0..8).map(|i| i.to_string()).collect::<Vec<String>>());
+        assert_eq!(stacked.get_column("v").unwrap().to_options::<i32>().unwrap().len(), 8);
+    }
+}
@@ -1,4 +1,16 @@
+pub mod cast;
+pub mod column_data;
 pub mod data_loader;
 pub mod data_matrix;
 pub mod data_vector;
-pub mod enums;
\ No newline at end of file
+pub mod data_writer;
+pub mod enums;
+pub mod group_by;
+pub mod join;
+mod random;
+pub mod reshape;
+pub mod schema;
+#[cfg(feature = "serde")]
+mod serde_validation;
+pub mod statistics;
+pub mod temporal;
\ No newline at end of file
@@ -0,0 +1,240 @@
+use std::collections::{HashMap, HashSet};
+
+use indexmap::{IndexMap, IndexSet};
+use veracity_types::dtype::DType;
+
+use crate::{data_matrix::DataMatrix, data_vector::DataVector, enums::{aggregation::Aggregation, error_types::DataLoaderError}, join::{common_dtype, insert_column, promote}};
+
+const VARIABLE_COLUMN: &str = "variable";
+const VALUE_COLUMN: &str = "value";
+const NULL_LABEL: &str = "null";
+
+// Text form of a value when it becomes a row or column label. Nulls stay None until the
+// labels are built so they cannot merge with a real "null" value.
+fn key_of(column: &DataVector, row: usize) -> Option<String> {
+    if column.is_null(row) {
+        None
+    } else {
+        Some(column.data.display_value(row).unwrap_or_default())
+    }
+}
+
+fn labels_of(keys: &IndexSet<Option<String>>, name: &str) -> Result<Vec<String>, DataLoaderError> {
+    if keys.contains(&None) && keys.contains(&Some(NULL_LABEL.to_string())) {
+        return Err(DataLoaderError::GenericError(format!("Column '{}' holds both nulls and the value '{}', which would share a label", name, NULL_LABEL)));
+    }
+
+    Ok(keys.iter().map(|key| key.clone().unwrap_or(NULL_LABEL.to_string())).collect())
+}
+
+fn widest_dtype<'a>(columns: impl Iterator<Item = &'a DataVector>) -> Result<Option<DType>, DataLoaderError> {
+    let mut dtype: Option<DType> = None;
+
+    for column in columns {
+        dtype = Some(match dtype {
+            Some(existing) => common_dtype(existing, column.dtype()).ok_or(DataLoaderError::HeterogeneousDataTypes)?,
+            None => column.dtype()
+        });
+    }
+
+    Ok(dtype)
+}
+
+impl DataMatrix {
+    // Long to wide: one row per distinct value of index, one column per distinct value of
+    // columns, and each cell the aggregate of values over the matching rows. Cells with no
+    // matching rows are null. Null keys are labelled "null".
+    pub fn pivot(&self, index: &str, columns: &str, values: &str, agg: Aggregation) -> Result<DataMatrix, DataLoaderError> {
+        if index == columns {
+            return Err(DataLoaderError::GenericError("pivot index and columns must be different columns".to_string()));
+        }
+
+        let value_label: String = format!("{}_{}", values, agg.name());
+        let long: DataMatrix = self.group_by(vec![index, columns])?.agg(vec![(values, agg)])?;
+
+        let row_keys: &DataVector = &long.columns[index];
+        let column_keys: &DataVector = &long.columns[columns];
+        let aggregated: &DataVector = &long.columns[&value_label];
+
+        let mut row_keys_seen: IndexSet<Option<String>> = IndexSet::new();
+        let mut column_keys_seen: IndexSet<Option<String>> = IndexSet::new();
+        let mut cells: HashMap<(usize, usize), usize> = HashMap::new();
+
+        for row in 0..long.num_rows() {
+            let (row_position, _) = row_keys_seen.insert_full(key_of(row_keys, row));
+            let (column_position, _) = column_keys_seen.insert_full(key_of(column_keys, row));
+            cells.insert((row_position, column_position), row);
+        }
+
+        let row_labels: Vec<String> = labels_of(&row_keys_seen, index)?;
+        let column_labels: Vec<String> = labels_of(&column_keys_seen, columns)?;
+        let mut wide: IndexMap<String, DataVector> = IndexMap::new();
+
+        for (column_position, label) in column_labels.into_iter().enumerate() {
+            let rows: Vec<Option<usize>> = (0..row_labels.len()).map(|row_position| cells.get(&(row_position, column_position)).copied()).collect();
+            insert_column(&mut wide, label, aggregated.take_optional(&rows)?)?;
+        }
+
+        let mut pivoted: DataMatrix = DataMatrix { columns: wide, index: Vec::new() };
+        pivoted.set_index(row_labels.iter().map(String::as_str).collect())?;
+
+        Ok(pivoted)
+    }
+
+    // Wide to long: the id_vars columns are repeated for every value_vars column, next to
+    // a "variable" column naming it and a "value" column holding its values. value_vars
+    // defaults to every column not in id_vars.
+    pub fn melt(&self, id_vars: Vec<&str>, value_vars: Vec<&str>) -> Result<DataMatrix, DataLoaderError> {
+        for &name in id_vars.iter().chain(value_vars.iter()) {
+            if !self.columns.contains_key(name) {
+                return Err(DataLoaderError::GenericError(format!("Column '{}' does not exist", name)));
+            }
+        }
+        if id_vars.contains(&VARIABLE_COLUMN) || id_vars.contains(&VALUE_COLUMN) {
+            return Err(DataLoaderError::DuplicateLabel);
+        }
+
+        let value_vars: Vec<&str> = if value_vars.is_empty() {
+            self.columns.keys().map(String::as_str).filter(|name| !id_vars.contains(name)).collect()
+        } else {
+            value_vars
+        };
+
+        let parts: Vec<DataMatrix> = value_vars
+            .iter()
+            .map(|&name| {
+                let mut part: DataMatrix = self.get_columns(id_vars.clone())?;
+                part.add_column(vec![name.to_string(); self.num_rows()], Some(VARIABLE_COLUMN))?;
+
+                let mut value: DataVector = self.columns[name].clone();
+                value.add_label(VALUE_COLUMN);
+                part.columns.insert(VALUE_COLUMN.to_string(), value);
+
+                Ok(part)
+            })
+            .collect::<Result<Vec<DataMatrix>, DataLoaderError>>()?;
+
+        DataMatrix::concat(parts.iter().collect(), true)
+    }
+
+    // Swaps rows and columns. All columns must share a dtype; the index labels become
+    // the column names and the column names become the index.
+    pub fn transpose(&self) -> Result<DataMatrix, DataLoaderError> {
+        if self.columns.is_empty() {
+            return Ok(DataMatrix::new());
+        }
+        if self.is_type_heterogeneous() {
+            return Err(DataLoaderError::HeterogeneousDataTypes);
+        }
+
+        let dtype: DType = self.columns[0].dtype();
+        let mut columns: IndexMap<String, DataVector> = IndexMap::new();
+
+        for (row, label) in self.index_labels().into_iter().enumerate() {
+            let mut transposed: DataVector = DataVector::nulls(None, dtype, 0);
+            for column in self.columns.values() {
+                transposed.append(&column.take(&[row])?)?;
+            }
+            insert_column(&mut columns, label, transposed)?;
+        }
+
+        Ok(DataMatrix { columns, index: self.column_names() })
+    }
+
+    // Turns every cell into a row of a single "value" column, indexed by
+    // "{row}{separator}{column}" in row-major order. Columns of different numeric widths
+    // are widened; other mixes are rejected.
+    pub fn stack(&self, separator: &str) -> Result<DataMatrix, DataLoaderError> {
+        let dtype: DType = match widest_dtype(self.columns.values())? {
+            Some(dtype) => dtype,
+            None => return Ok(DataMatrix::new())
+        };
+
+        let num_rows: usize = self.num_rows();
+        let mut stacked: DataVector = DataVector::nulls(Some(VALUE_COLUMN.to_string()), dtype, 0);
+        for column in self.columns.values() {
+            stacked.append(&promote(column, dtype))?;
+        }
+
+        let mut rows: Vec<usize> = Vec::with_capacity(num_rows * self.columns.len());
+        let mut index: Vec<String> = Vec::with_capacity(num_rows * self.columns.len());
+
+        for (row, row_label) in self.index_labels().iter().enumerate() {
+            for (position, column_label) in self.columns.keys().enumerate() {
+                rows.push(position * num_rows + row);
+                index.push(format!("{}{}{}", row_label, separator, column_label));
+            }
+        }
+
+        if index.iter().collect::<HashSet<&String>>().len() != index.len() {
+            return Err(DataLoaderError::DuplicateIndex);
+        }
+
+        let mut columns: IndexMap<String, DataVector> = IndexMap::new();
+        columns.insert(VALUE_COLUMN.to_string(), stacked.take(&rows)?);
+
+        Ok(DataMatrix { columns, index })
+    }
+
+    // Inverse of stack: splits each index label at the last separator into a row label
+    // and a column label. With a single column the new columns are named by the column
+    // labels alone, otherwise "{column}{separator}{label}". Missing cells are null.
+    pub fn unstack(&self, separator: &str) -> Result<DataMatrix, DataLoaderError> {
+        let mut row_labels: IndexSet<String> = IndexSet::new();
+        let mut column_labels: IndexSet<String> = IndexSet::new();
+        let mut cells: HashMap<(usize, usize), usize> = HashMap::new();
+
+        for (row, label) in self.index_labels().iter().enumerate() {
+            let (row_label, column_label) = label
+                .rsplit_once(separator)
+                .ok_or(DataLoaderError::GenericError(format!("Index label '{}' does not contain the separator '{}'", label, separator)))?;
+
+            let (row_position, _) = row_labels.insert_full(row_label.to_string());
+            let (column_position, _) = column_labels.insert_full(column_label.to_string());
+            cells.insert((row_position, column_position), row);
+        }
+
+        let mut columns: IndexMap<String, DataVector> = IndexMap::new();
+
+        for (name, column) in self.columns.iter() {
+            for (column_position, column_label) in column_labels.iter().enumerate() {
+                let rows: Vec<Option<usize>> = (0..row_labels.len()).map(|row_position| cells.get(&(row_position, column_position)).copied()).collect();
+
+                let label: String = if self.columns.len() == 1 { column_label.clone() } else { format!("{}{}{}", name, separator, column_label) };
+                insert_column(&mut columns, label, column.take_optional(&rows)?)?;
+            }
+        }
+
+        Ok(DataMatrix {
+            columns,
+            index: row_labels.into_iter().collect()
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn long(keys: Vec<Option<&str>>) -> DataMatrix {
+        DataMatrix::from_vec(vec![
+            DataVector::from_nullable(Some("row".to_string()), vec![Some("r".to_string()); keys.len()]),
+            DataVector::from_nullable(Some("key".to_string()), keys.into_iter().map(|key| key.map(str::to_string)).collect::<Vec<Option<String>>>()),
+            DataVector::from_nullable(Some("value".to_string()), vec![Some(1i64); 2])
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn pivot_labels_null_keys() {
+        let pivoted: DataMatrix = long(vec![None, Some("a")]).pivot("row", "key", "value", Aggregation::Sum).unwrap();
+        assert_eq!(pivoted.column_names(), vec!["null".to_string(), "a".to_string()]);
+    }
+
+    #[test]
+    fn pivot_rejects_null_keys_clashing_with_null_text() {
+        let result: Result<DataMatrix, DataLoaderError> = long(vec![None, Some("null")]).pivot("row", "key", "value", Aggregation::Sum);
+        assert!(matches!(result, Err(DataLoaderError::GenericError(_))));
+    }
+}
@@ -0,0 +1,175 @@
+use veracity_types::dtype::DType;
+
+use crate::{data_matrix::DataMatrix, data_vector::DataVector, enums::schema_violation::SchemaViolation};
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColumnSchema {
+    pub name: String,
+    pub dtype: DType,
+    pub nullable: bool,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub allowed_values: Option<Vec<String>>
+}
+
+impl ColumnSchema {
+    pub fn new(name: &str, dtype: DType) -> Self {
+        ColumnSchema {
+            name: name.to_string(),
+            dtype,
+            nullable: true,
+            min: None,
+            max: None,
+            allowed_values: None
+        }
+    }
+}
+
+// Columns not listed are ignored unless strict is set.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Schema {
+    pub columns: Vec<ColumnSchema>,
+    pub strict: bool
+}
+
+impl Schema {
+    pub fn new(columns: Vec<ColumnSchema>) -> Self {
+        Schema {
+            columns,
+            strict: false
+        }
+    }
+
+    pub fn column(&self, name: &str) -> Option<&ColumnSchema> {
+        self.columns.iter().find(|column| column.name == name)
+    }
+
+    pub fn dtype(&self, name: &str) -> Option<DType> {
+        self.column(name).map(|column| column.dtype)
+    }
+}
+
+fn validate_column(column: &DataVector, schema: &ColumnSchema, index: &[String], violations: &mut Vec<SchemaViolation>) {
+    if column.dtype() != schema.dtype {
+        violations.push(SchemaViolation::WrongDType { column: schema.name.clone(), expected: schema.dtype, actual: column.dtype() });
+        return;
+    }
+
+    for (row, label) in index.iter().enumerate() {
+        if column.is_null(row) {
+            if !schema.nullable {
+                violations.push(SchemaViolation::NullValue { column: schema.name.clone(), row: label.clone() });
+            }
+            continue;
+        }
+
+        if let Some(value) = column.data.value_as_f64(row)
+            && (schema.min.is_some_and(|min| value < min) || schema.max.is_some_and(|max| value > max)) {
+            violations.push(SchemaViolation::OutOfRange { column: schema.name.clone(), row: label.clone(), value });
+        }
+
+        if let Some(allowed) = &schema.allowed_values
+            && let Some(value) = column.data.display_value(row)
+            && !allowed.contains(&value) {
+            violations.push(SchemaViolation::UnknownValue { column: schema.name.clone(), row: label.clone(), value });
+        }
+    }
+}
+
+impl DataMatrix {
+    // Checks every column against the schema and returns all violations found; an empty
+    // list means the matrix conforms. Value checks are skipped for columns of the wrong dtype.
+    pub fn validate(&self, schema: &Schema) -> Vec<SchemaViolation> {
+        let index: Vec<String> = self.index_labels();
+        let mut violations: Vec<SchemaViolation> = Vec::new();
+
+        for column_schema in schema.columns.iter() {
+            match self.columns.get(&column_schema.name) {
+                Some(column) => validate_column(column, column_schema, &index, &mut violations),
+                None => violations.push(SchemaViolation::MissingColumn(column_schema.name.clone()))
+            }
+        }
+
+        if schema.strict {
+            violations.extend(self.columns.keys().filter(|name| schema.column(name).is_none()).map(|name| SchemaViolation::UnexpectedColumn(name.clone())));
+        }
+
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{data_loader::csv_loader::{CSVLoader, CSVLoaderSettings}, enums::error_types::DataLoaderError};
+
+    use super::*;
+
+    fn matrix() -> DataMatrix {
+        let mut matrix: DataMatrix = DataMatrix::from_vec(vec![
+            DataVector::from_nullable(Some("age".to_string()), vec![Some(30i64), None, Some(150)]),
+            DataVector::from_nullable(Some("grade".to_string()), vec![Some("a".to_string()), Some("b".to_string()), Some("z".to_string())]),
+            DataVector::from_nullable(Some("height".to_string()), vec![Some(1.8f32), Some(1.6), Some(1.7)]),
+            DataVector::from_nullable(Some("extra".to_string()), vec![Some(true), Some(false), None])
+        ])
+        .unwrap();
+        matrix.set_index(vec!["r0", "r1", "r2"]).unwrap();
+        matrix
+    }
+
+    fn schema() -> Schema {
+        Schema {
+            columns: vec![
+                ColumnSchema { nullable: false, min: Some(0.0), max: Some(120.0), ..ColumnSchema::new("age", DType::I64) },
+                ColumnSchema { allowed_values: Some(vec!["a".to_string(), "b".to_string()]), ..ColumnSchema::new("grade", DType::Utf8) },
+                ColumnSchema { min: Some(0.0), ..ColumnSchema::new("height", DType::F64) },
+                ColumnSchema::new("name", DType::Utf8)
+            ],
+            strict: true
+        }
+    }
+
+    #[test]
+    fn validate_reports_every_violation() {
+        let violations: Vec<SchemaViolation> = matrix().validate(&schema());
+
+        assert_eq!(violations, vec![
+            SchemaViolation::NullValue { column: "age".to_string(), row: "r1".to_string() },
+            SchemaViolation::OutOfRange { column: "age".to_string(), row: "r2".to_string(), value: 150.0 },
+            SchemaViolation::UnknownValue { column: "grade".to_string(), row: "r2".to_string(), value: "z".to_string() },
+            SchemaViolation::WrongDType { column: "height".to_string(), expected: DType::F64, actual: DType::F32 },
+            SchemaViolation::MissingColumn("name".to_string()),
+            SchemaViolation::UnexpectedColumn("extra".to_string())
+        ]);
+    }
+
+    #[test]
+    fn conforming_matrix_has_no_violations() {
+        let schema: Schema = Schema::new(vec![
+            ColumnSchema { min: Some(0.0), ..ColumnSchema::new("age", DType::I64) },
+            ColumnSchema { allowed_values: Some(vec!["a".to_string(), "b".to_string(), "z".to_string()]), ..ColumnSchema::new("grade", DType::Utf8) }
+        ]);
+
+        assert!(matrix().validate(&schema).is_empty());
+    }
+
+    #[test]
+    fn csv_schema_forces_dtypes_over_dtypes_and_inference() {
+        let settings: CSVLoaderSettings = CSVLoaderSettings {
+            dtypes: HashMap::from([("id".to_string(), DType::I64)]),
+            schema: Some(Schema::new(vec![ColumnSchema::new("id", DType::F64), ColumnSchema::new("code", DType::Utf8)])),
+            ..Default::default()
+        };
+        let matrix: DataMatrix = CSVLoader::new(settings.clone()).load_from_reader("id,code,n\n1,007,2\n3,010,4\n".as_bytes()).unwrap();
+
+        assert_eq!(matrix.get_column("id").unwrap().dtype(), DType::F64);
+        assert_eq!(matrix.get_column("code").unwrap().to_options::<String>().unwrap(), vec![Some("007".to_string()), Some("010".to_string())]);
+        assert_eq!(matrix.get_column("n").unwrap().dtype(), DType::I64);
+
+        let missing: Result<DataMatrix, DataLoaderError> = CSVLoader::new(settings).load_from_reader("id,n\n1,2\n".as_bytes());
+        assert!(matches!(missing, Err(DataLoaderError::ColumnMismatch { missing, .. }) if missing == vec!["code".to_string()]));
+    }
+}
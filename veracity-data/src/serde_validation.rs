@@ -0,0 +1,119 @@
+use indexmap::IndexMap;
+
+use crate::{column_data::ColumnData, data_matrix::DataMatrix, data_vector::DataVector, enums::error_types::DataLoaderError};
+
+// Deserialized forms of DataVector and DataMatrix. Payloads are checked before they are
+// turned into the real types, since the rest of the crate indexes data, validity and
+// categorical codes on the assumption that they agree with len.
+#[derive(serde::Deserialize)]
+pub(crate) struct RawDataVector {
+    label: Option<String>,
+    data: ColumnData,
+    len: usize,
+    validity: Option<Vec<bool>>
+}
+
+#[derive(serde::Deserialize)]
+pub(crate) struct RawDataMatrix {
+    columns: IndexMap<String, DataVector>,
+    index: Vec<String>
+}
+
+impl TryFrom<RawDataVector> for DataVector {
+    type Error = DataLoaderError;
+
+    fn try_from(raw: RawDataVector) -> Result<Self, Self::Error> {
+        let label: &str = raw.label.as_deref().unwrap_or_default();
+
+        if raw.data.len() != raw.len {
+            return Err(DataLoaderError::GenericError(format!("Column '{}' has {} values but a len of {}", label, raw.data.len(), raw.len)));
+        }
+        if let Some(validity) = &raw.validity && validity.len() != raw.len {
+            return Err(DataLoaderError::GenericError(format!("Column '{}' has {} validity flags but a len of {}", label, validity.len(), raw.len)));
+        }
+
+        // Null slots may hold any code, so only valid rows have to name a category.
+        if let ColumnData::Categorical(categorical) = &raw.data {
+            let invalid_code: Option<&u32> = categorical
+                .codes
+                .iter()
+                .enumerate()
+                .filter(|(row, _)| raw.validity.as_ref().is_none_or(|validity| validity[*row]))
+                .map(|(_, code)| code)
+                .find(|&&code| code as usize >= categorical.categories.len());
+
+            if let Some(code) = invalid_code {
+                return Err(DataLoaderError::GenericError(format!("Column '{}' has code {} but only {} categories", label, code, categorical.categories.len())));
+            }
+        }
+
+        Ok(DataVector {
+            label: raw.label,
+            data: raw.data,
+            len: raw.len,
+            validity: raw.validity
+        })
+    }
+}
+
+impl TryFrom<RawDataMatrix> for DataMatrix {
+    type Error = DataLoaderError;
+
+    fn try_from(raw: RawDataMatrix) -> Result<Self, Self::Error> {
+        let num_rows: Option<usize> = raw.columns.values().next().map(|column| column.len);
+
+        if raw.columns.values().any(|column| Some(column.len) != num_rows) {
+            return Err(DataLoaderError::HeterogeneousColumnLengths);
+        }
+        if !raw.index.is_empty() && num_rows.is_some_and(|num_rows| num_rows != raw.index.len()) {
+            return Err(DataLoaderError::IndexRowCountMismatch);
+        }
+
+        Ok(DataMatrix {
+            columns: raw.columns,
+            index: raw.index
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matrix() -> DataMatrix {
+        let mut matrix: DataMatrix = DataMatrix::from_vec(vec![
+            DataVector::from_nullable(Some("x".to_string()), vec![Some(1.5), None, Some(3.0)]),
+            DataVector::from_categorical(Some("kind".to_string()), vec![Some("a".to_string()), Some("b".to_string()), None])
+        ])
+        .unwrap();
+        matrix.set_index(vec!["r0", "r1", "r2"]).unwrap();
+        matrix
+    }
+
+    #[test]
+    fn matrices_round_trip() {
+        let original: DataMatrix = matrix();
+        let restored: DataMatrix = serde_json::from_str(&serde_json::to_string(&original).unwrap()).unwrap();
+
+        assert_eq!(restored.index, original.index);
+        assert_eq!(restored.column_names(), original.column_names());
+        assert_eq!(restored.get_column("x").unwrap().to_options::<f64>().unwrap(), vec![Some(1.5), None, Some(3.0)]);
+        assert_eq!(restored.get_column("kind").unwrap().to_options::<String>().unwrap(), vec![Some("a".to_string()), Some("b".to_string()), None]);
+    }
+
+    #[test]
+    fn malformed_payloads_are_rejected() {
+        let payload: serde_json::Value = serde_json::to_value(matrix()).unwrap();
+        let malformed = |path: &str, value: serde_json::Value| {
+            let mut payload: serde_json::Value = payload.clone();
+            *payload.pointer_mut(path).unwrap() = value;
+            serde_json::from_value::<DataMatrix>(payload).is_err()
+        };
+
+        assert!(malformed("/columns/x/len", serde_json::json!(4)));
+        assert!(malformed("/columns/x/validity", serde_json::json!([true, false])));
+        assert!(malformed("/columns/x/data/F64", serde_json::json!([1.5, 0.0])));
+        assert!(malformed("/columns/kind/data/Categorical/codes/1", serde_json::json!(7)));
+        assert!(malformed("/index", serde_json::json!(["r0"])));
+    }
+}
@@ -0,0 +1,368 @@
+use std::fmt::Write;
+
+use indexmap::IndexMap;
+
+use crate::{data_matrix::DataMatrix, data_vector::DataVector, enums::{correlation_method::CorrelationMethod, error_types::DataLoaderError}};
+
+const NUMERIC_STATISTICS: [&str; 10] = ["count", "null_count", "mean", "std", "min", "25%", "50%", "75%", "max", "unique"];
+const CATEGORICAL_STATISTICS: [&str; 5] = ["count", "null_count", "unique", "top", "freq"];
+
+fn numeric_values(column: &DataVector) -> Vec<f64> {
+    (0..column.len)
+        .filter(|&row| !column.is_null(row))
+        .filter_map(|row| column.data.value_as_f64(row))
+        .collect()
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() { None } else { Some(values.iter().sum::<f64>() / values.len() as f64) }
+}
+
+// Sample standard deviation, matching pandas.
+fn std(values: &[f64]) -> Option<f64> {
+    if values.len() < 2 {
+        return None;
+    }
+    let mean: f64 = mean(values)?;
+    Some((values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64).sqrt())
+}
+
+// Linear interpolation between the closest ranks of already sorted values.
+fn quantile(sorted: &[f64], q: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let position: f64 = q * (sorted.len() - 1) as f64;
+    let lower: usize = position.floor() as usize;
+    let upper: usize = position.ceil() as usize;
+    Some(sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64))
+}
+
+// Ranks starting at 1, with tied values sharing their average rank.
+fn ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+
+    let mut ranks: Vec<f64> = vec![0.0; values.len()];
+    let mut start: usize = 0;
+    while start < order.len() {
+        let mut end: usize = start;
+        while end + 1 < order.len() && values[order[end + 1]] == values[order[start]] {
+            end += 1;
+        }
+        let rank: f64 = (start + end) as f64 / 2.0 + 1.0;
+        for &row in &order[start..=end] {
+            ranks[row] = rank;
+        }
+        start = end + 1;
+    }
+
+    ranks
+}
+
+fn pearson(x: &[f64], y: &[f64]) -> Option<f64> {
+    let mean_x: f64 = mean(x)?;
+    let mean_y: f64 = mean(y)?;
+
+    let covariance: f64 = x.iter().zip(y).map(|(a, b)| (a - mean_x) * (b - mean_y)).sum();
+    let spread_x: f64 = x.iter().map(|a| (a - mean_x).powi(2)).sum::<f64>().sqrt();
+    let spread_y: f64 = y.iter().map(|b| (b - mean_y).powi(2)).sum::<f64>().sqrt();
+
+    if x.len() < 2 || spread_x == 0.0 || spread_y == 0.0 {
+        return None;
+    }
+    Some(covariance / (spread_x * spread_y))
+}
+
+// Kendall's tau-b, which accounts for ties in either variable.
+fn kendall(x: &[f64], y: &[f64]) -> Option<f64> {
+    let n: usize = x.len();
+    let (mut concordant, mut discordant, mut ties_x, mut ties_y): (f64, f64, f64, f64) = (0.0, 0.0, 0.0, 0.0);
+
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let dx: f64 = x[i] - x[j];
+            let dy: f64 = y[i] - y[j];
+            if dx == 0.0 {
+                ties_x += 1.0;
+            }
+            if dy == 0.0 {
+                ties_y += 1.0;
+            }
+            if dx * dy > 0.0 {
+                concordant += 1.0;
+            } else if dx * dy < 0.0 {
+                discordant += 1.0;
+            }
+        }
+    }
+
+    let pairs: f64 = (n * n.saturating_sub(1)) as f64 / 2.0;
+    let denominator: f64 = ((pairs - ties_x) * (pairs - ties_y)).sqrt();
+
+    if denominator == 0.0 {
+        return None;
+    }
+    Some((concordant - discordant) / denominator)
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn html_table(matrix: &DataMatrix) -> String {
+    let mut html: String = String::from("<table>\n<tr><th></th>");
+    for label in matrix.columns.keys() {
+        let _ = write!(html, "<th>{}</th>", escape_html(label));
+    }
+    html.push_str("</tr>\n");
+
+    for (row, index) in matrix.index_labels().iter().enumerate() {
+        let _ = write!(html, "<tr><th>{}</th>", escape_html(index));
+        for column in matrix.columns.values() {
+            let _ = write!(html, "<td>{}</td>", escape_html(&column.format_value(row)));
+        }
+        html.push_str("</tr>\n");
+    }
+
+    html.push_str("</table>\n");
+    html
+}
+
+impl DataVector {
+    // Distinct non-null values with how often each occurs, most frequent first. Ties
+    // keep the order the values first appear in.
+    pub fn value_counts(&self) -> Result<DataMatrix, DataLoaderError> {
+        let mut counts: IndexMap<String, (usize, i64)> = IndexMap::new();
+
+        for row in (0..self.len).filter(|&row| !self.is_null(row)) {
+            let key: String = self.data.format_debug(row).unwrap_or_default();
+            counts.entry(key).or_insert((row, 0)).1 += 1;
+        }
+
+        let mut entries: Vec<(usize, i64)> = counts.into_values().collect();
+        entries.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+
+        let mut values: DataVector = self.take(&entries.iter().map(|&(row, _)| row).collect::<Vec<usize>>())?;
+        values.add_label("value");
+
+        let mut matrix: DataMatrix = DataMatrix::new();
+        matrix.columns.insert("value".to_string(), values);
+        matrix.add_column(entries.iter().map(|&(_, count)| count).collect::<Vec<i64>>(), Some("count"))?;
+        matrix.index = (0..entries.len()).map(|i| format!("{}", i)).collect();
+
+        Ok(matrix)
+    }
+}
+
+impl DataMatrix {
+    // Summary statistics per column, one row per statistic. Numeric columns report
+    // count, null_count, mean, std, min, quartiles, max and unique as f64; string and
+    // bool columns report count, null_count, unique, top and freq as text. Statistics
+    // that do not apply to a column are null.
+    pub fn describe(&self) -> Result<DataMatrix, DataLoaderError> {
+        let has_numeric: bool = self.columns.values().any(|column| column.dtype().is_numeric());
+        let has_categorical: bool = self.columns.values().any(|column| !column.dtype().is_numeric());
+
+        let mut statistics: Vec<&str> = Vec::new();
+        if has_numeric {
+            statistics.extend(NUMERIC_STATISTICS);
+        }
+        if has_categorical {
+            for statistic in CATEGORICAL_STATISTICS {
+                if !statistics.contains(&statistic) {
+                    statistics.push(statistic);
+                }
+            }
+        }
+
+        let mut columns: IndexMap<String, DataVector> = IndexMap::new();
+
+        for (label, column) in self.columns.iter() {
+            let count: usize = column.len - column.null_count();
+            let counts: DataMatrix = column.value_counts()?;
+            let unique: usize = counts.num_rows();
+
+            let summary: DataVector = if column.dtype().is_numeric() {
+                let mut sorted: Vec<f64> = numeric_values(column);
+                sorted.sort_by(f64::total_cmp);
+
+                let values: Vec<Option<f64>> = statistics
+                    .iter()
+                    .map(|&statistic| match statistic {
+                        "count" => Some(count as f64),
+                        "null_count" => Some(column.null_count() as f64),
+                        "mean" => mean(&sorted),
+                        "std" => std(&sorted),
+                        "min" => sorted.first().copied(),
+                        "25%" => quantile(&sorted, 0.25),
+                        "50%" => quantile(&sorted, 0.5),
+                        "75%" => quantile(&sorted, 0.75),
+                        "max" => sorted.last().copied(),
+                        "unique" => Some(unique as f64),
+                        _ => None
+                    })
+                    .collect();
+                DataVector::from_nullable(Some(label.clone()), values)
+            } else {
+                let top: Option<String> = if unique > 0 { counts.columns["value"].data.display_value(0) } else { None };
+                let freq: Option<String> = if unique > 0 { counts.columns["count"].data.display_value(0) } else { None };
+
+                let values: Vec<Option<String>> = statistics
+                    .iter()
+                    .map(|&statistic| match statistic {
+                        "count" => Some(count.to_string()),
+                        "null_count" => Some(column.null_count().to_string()),
+                        "unique" => Some(unique.to_string()),
+                        "top" => top.clone(),
+                        "freq" => freq.clone(),
+                        _ => None
+                    })
+                    .collect();
+                DataVector::from_nullable(Some(label.clone()), values)
+            };
+
+            columns.insert(label.clone(), summary);
+        }
+
+        Ok(DataMatrix {
+            columns,
+            index: statistics.iter().map(|statistic| statistic.to_string()).collect()
+        })
+    }
+
+    // Pairwise correlation between the numeric columns, using the rows where both
+    // columns are non-null. Pairs without enough variation to correlate are null.
+    pub fn corr(&self, method: CorrelationMethod) -> Result<DataMatrix, DataLoaderError> {
+        let numeric: Vec<(&String, &DataVector)> = self.columns.iter().filter(|(_, column)| column.dtype().is_numeric()).collect();
+        let mut columns: IndexMap<String, DataVector> = IndexMap::new();
+
+        for &(label, column) in numeric.iter() {
+            let values: Vec<Option<f64>> = numeric
+                .iter()
+                .map(|&(_, other)| {
+                    let (x, y): (Vec<f64>, Vec<f64>) = (0..column.len)
+                        .filter(|&row| !column.is_null(row) && !other.is_null(row))
+                        .filter_map(|row| Some((column.data.value_as_f64(row)?, other.data.value_as_f64(row)?)))
+                        .unzip();
+
+                    match method {
+                        CorrelationMethod::Pearson => pearson(&x, &y),
+                        CorrelationMethod::Spearman => pearson(&ranks(&x), &ranks(&y)),
+                        CorrelationMethod::Kendall => kendall(&x, &y)
+                    }
+                })
+                .collect();
+
+            columns.insert(label.clone(), DataVector::from_nullable(Some(label.clone()), values));
+        }
+
+        Ok(DataMatrix {
+            columns,
+            index: numeric.iter().map(|(label, _)| label.to_string()).collect()
+        })
+    }
+
+    // Writes a standalone HTML report with an overview, the describe table, the most
+    // frequent values of every non-numeric column and the Pearson correlations.
+    pub fn write_profile(&self, path: &str) -> Result<(), DataLoaderError> {
+        let null_cells: usize = self.columns.values().map(|column| column.null_count()).sum();
+
+        let mut html: String = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Data profile</title>\n<style>\n");
+        html.push_str("body { font-family: sans-serif; margin: 2em; }\ntable { border-collapse: collapse; margin-bottom: 2em; }\nth, td { border: 1px solid #ccc; padding: 4px 8px; text-align: right; }\nth { background: #f4f4f4; }\n");
+        html.push_str("</style>\n</head>\n<body>\n<h1>Data profile</h1>\n");
+
+        let _ = write!(html, "<h2>Overview</h2>\n<p>{} rows, {} columns, {} null cells</p>\n", self.num_rows(), self.columns.len(), null_cells);
+        html.push_str("<table>\n<tr><th>Column</th><th>Type</th><th>Nulls</th></tr>\n");
+        for (label, column) in self.columns.iter() {
+            let _ = writeln!(html, "<tr><td>{}</td><td>{}</td><td>{}</td></tr>", escape_html(label), column.dtype(), column.null_count());
+        }
+        html.push_str("</table>\n");
+
+        html.push_str("<h2>Summary statistics</h2>\n");
+        html.push_str(&html_table(&self.describe()?));
+
+        for (label, column) in self.columns.iter().filter(|(_, column)| !column.dtype().is_numeric()) {
+            let _ = writeln!(html, "<h2>Most frequent values: {}</h2>", escape_html(label));
+            html.push_str(&html_table(&column.value_counts()?.head(10)?));
+        }
+
+        if self.columns.values().filter(|column| column.dtype().is_numeric()).count() > 1 {
+            html.push_str("<h2>Correlations (Pearson)</h2>\n");
+            html.push_str(&html_table(&self.corr(CorrelationMethod::Pearson)?));
+        }
+
+        html.push_str("</body>\n</html>\n");
+
+        std::fs::write(path, html).map_err(|e| DataLoaderError::FileWrite(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[Option<f64>], expected: &[Option<f64>]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            match (a, e) {
+                (Some(a), Some(e)) => assert!((a - e).abs() < 1e-12, "{:?} != {:?}", actual, expected),
+                _ => assert_eq!(a, e)
+            }
+        }
+    }
+
+    fn strings(values: &[Option<&str>]) -> Vec<Option<String>> {
+        values.iter().map(|value| value.map(str::to_string)).collect()
+    }
+
+    #[test]
+    fn describe_interpolates_quantiles_like_pandas() {
+        let matrix: DataMatrix = DataMatrix::from_vec(vec![
+            DataVector::from_nullable(Some("x".to_string()), vec![Some(4.0), None, Some(1.0), Some(3.0), Some(2.0)]),
+            DataVector::from_nullable(Some("s".to_string()), strings(&[Some("b"), Some("a"), None, Some("b"), Some("c")]))
+        ])
+        .unwrap();
+        let described: DataMatrix = matrix.describe().unwrap();
+
+        assert_eq!(described.index, vec!["count", "null_count", "mean", "std", "min", "25%", "50%", "75%", "max", "unique", "top", "freq"]);
+        assert_close(&described.get_column("x").unwrap().to_options::<f64>().unwrap(), &[
+            Some(4.0), Some(1.0), Some(2.5), Some((5.0f64 / 3.0).sqrt()), Some(1.0), Some(1.75), Some(2.5), Some(3.25), Some(4.0), Some(4.0), None, None
+        ]);
+        assert_eq!(described.get_column("s").unwrap().to_options::<String>().unwrap(), strings(&[
+            Some("4"), Some("1"), None, None, None, None, None, None, None, Some("3"), Some("b"), Some("2")
+        ]));
+    }
+
+    #[test]
+    fn value_counts_sort_by_count_then_first_appearance() {
+        let column: DataVector = DataVector::from_nullable(Some("s".to_string()), strings(&[Some("c"), Some("a"), None, Some("b"), Some("a"), Some("b")]));
+        let counts: DataMatrix = column.value_counts().unwrap();
+
+        assert_eq!(counts.get_column("value").unwrap().to_options::<String>().unwrap(), strings(&[Some("a"), Some("b"), Some("c")]));
+        assert_eq!(counts.get_column("count").unwrap().to_options::<i64>().unwrap(), vec![Some(2), Some(2), Some(1)]);
+    }
+
+    fn correlations(method: CorrelationMethod) -> Vec<Option<f64>> {
+        let matrix: DataMatrix = DataMatrix::from_vec(vec![
+            DataVector::from_nullable(Some("x".to_string()), vec![Some(1i64), Some(2), Some(2), Some(3), Some(4), None]),
+            DataVector::from_nullable(Some("y".to_string()), vec![Some(1.0), Some(3.0), Some(2.0), Some(2.0), Some(5.0), Some(9.0)]),
+            DataVector::from_nullable(Some("constant".to_string()), vec![Some(1.0); 6]),
+            DataVector::from_nullable(Some("s".to_string()), strings(&[Some("a"); 6]))
+        ])
+        .unwrap();
+        let correlated: DataMatrix = matrix.corr(method).unwrap();
+
+        assert_eq!(correlated.index, vec!["x", "y", "constant"]);
+        correlated.get_column("x").unwrap().to_options::<f64>().unwrap()
+    }
+
+    #[test]
+    fn corr_matches_pandas_on_tied_values() {
+        assert_close(&correlations(CorrelationMethod::Pearson), &[Some(1.0), Some(0.8385566513510482), None]);
+        // Tied values share their average rank.
+        assert_close(&correlations(CorrelationMethod::Spearman), &[Some(1.0), Some(7.25 / 9.5), None]);
+        // Tau-b: 7 concordant and 1 discordant pair, with one tie in each column.
+        assert_close(&correlations(CorrelationMethod::Kendall), &[Some(1.0), Some(6.0 / 9.0), None]);
+    }
+}
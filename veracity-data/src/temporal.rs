@@ -0,0 +1,150 @@
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, TimeDelta, Timelike};
+use veracity_types::dtype::DType;
+
+use crate::{column_data::{ColumnData, ColumnType}, data_vector::DataVector, enums::error_types::DataLoaderError};
+
+const DEFAULT_DATE_FORMATS: [&str; 5] = ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M", "%Y-%m-%d"];
+
+pub(crate) fn default_date_formats() -> Vec<String> {
+    DEFAULT_DATE_FORMATS.iter().map(|format| format.to_string()).collect()
+}
+
+// Tries each chrono format in turn; formats without a time part give midnight. Values
+// carrying a UTC offset (RFC 3339) are converted to UTC.
+pub(crate) fn parse_datetime(value: &str, formats: &[String]) -> Option<i64> {
+    for format in formats {
+        if let Ok(datetime) = NaiveDateTime::parse_from_str(value, format) {
+            return Some(datetime.and_utc().timestamp_micros());
+        }
+        if let Ok(date) = NaiveDate::parse_from_str(value, format) {
+            return date.and_hms_opt(0, 0, 0).map(|datetime| datetime.and_utc().timestamp_micros());
+        }
+    }
+
+    DateTime::parse_from_rfc3339(value).ok().map(|datetime| datetime.timestamp_micros())
+}
+
+impl DataVector {
+    pub fn from_datetimes(values: Vec<Option<NaiveDateTime>>) -> Self {
+        Self::from_micros(None, values.into_iter().map(|v| v.map(|v| v.and_utc().timestamp_micros())).collect(), DType::Datetime)
+    }
+
+    pub fn from_durations(values: Vec<Option<TimeDelta>>) -> Self {
+        Self::from_micros(None, values.into_iter().map(|v| v.and_then(|v| v.num_microseconds())).collect(), DType::Duration)
+    }
+
+    pub fn to_datetimes(&self) -> Result<Vec<Option<NaiveDateTime>>, DataLoaderError> {
+        Ok(self.micros(DType::Datetime)?.into_iter().map(|v| v.and_then(DateTime::from_timestamp_micros).map(|v| v.naive_utc())).collect())
+    }
+
+    pub fn to_durations(&self) -> Result<Vec<Option<TimeDelta>>, DataLoaderError> {
+        Ok(self.micros(DType::Duration)?.into_iter().map(|v| v.map(TimeDelta::microseconds)).collect())
+    }
+
+    // Parses a string column into datetimes using the given chrono formats, or a set of
+    // ISO 8601 formats when none are given.
+    pub fn to_datetime(&self, formats: &[&str]) -> Result<DataVector, DataLoaderError> {
+        let formats: Vec<String> = if formats.is_empty() { default_date_formats() } else { formats.iter().map(|format| format.to_string()).collect() };
+
+        let values: Vec<Option<i64>> = self
+            .to_options::<String>()?
+            .into_iter()
+            .map(|value| match value {
+                Some(value) => parse_datetime(&value, &formats)
+                    .map(Some)
+                    .ok_or(DataLoaderError::ParseError(format!("'{}' does not match any of the date formats", value))),
+                None => Ok(None)
+            })
+            .collect::<Result<Vec<Option<i64>>, DataLoaderError>>()?;
+
+        Ok(Self::from_micros(self.label.clone(), values, DType::Datetime))
+    }
+
+    // Time elapsed from earlier to each value of this column, row by row.
+    pub fn duration_since(&self, earlier: &DataVector) -> Result<DataVector, DataLoaderError> {
+        if self.len != earlier.len {
+            return Err(DataLoaderError::RowCountMismatch);
+        }
+
+        let values: Vec<Option<i64>> = self
+            .micros(DType::Datetime)?
+            .into_iter()
+            .zip(earlier.micros(DType::Datetime)?)
+            .map(|(later, earlier)| later.zip(earlier).and_then(|(later, earlier)| later.checked_sub(earlier)))
+            .collect();
+
+        Ok(Self::from_micros(self.label.clone(), values, DType::Duration))
+    }
+
+    pub fn year(&self) -> Result<DataVector, DataLoaderError> {
+        self.datetime_feature("year", |datetime| datetime.year())
+    }
+
+    pub fn month(&self) -> Result<DataVector, DataLoaderError> {
+        self.datetime_feature("month", |datetime| datetime.month() as i32)
+    }
+
+    pub fn day(&self) -> Result<DataVector, DataLoaderError> {
+        self.datetime_feature("day", |datetime| datetime.day() as i32)
+    }
+
+    // Monday is 0 and Sunday is 6.
+    pub fn day_of_week(&self) -> Result<DataVector, DataLoaderError> {
+        self.datetime_feature("day_of_week", |datetime| datetime.weekday().num_days_from_monday() as i32)
+    }
+
+    pub fn hour(&self) -> Result<DataVector, DataLoaderError> {
+        self.datetime_feature("hour", |datetime| datetime.hour() as i32)
+    }
+
+    pub fn minute(&self) -> Result<DataVector, DataLoaderError> {
+        self.datetime_feature("minute", |datetime| datetime.minute() as i32)
+    }
+
+    pub fn second(&self) -> Result<DataVector, DataLoaderError> {
+        self.datetime_feature("second", |datetime| datetime.second() as i32)
+    }
+
+    // Whole seconds since the Unix epoch.
+    pub fn epoch(&self) -> Result<DataVector, DataLoaderError> {
+        self.datetime_feature("epoch", |datetime| datetime.and_utc().timestamp())
+    }
+
+    pub fn total_seconds(&self) -> Result<DataVector, DataLoaderError> {
+        let values: Vec<Option<f64>> = self.micros(DType::Duration)?.into_iter().map(|v| v.map(|v| v as f64 / 1_000_000.0)).collect();
+        Ok(Self::from_nullable(self.feature_label("total_seconds"), values))
+    }
+
+    pub(crate) fn from_micros(label: Option<String>, values: Vec<Option<i64>>, dtype: DType) -> Self {
+        let mut column: DataVector = Self::from_nullable(label, values);
+
+        if let ColumnData::I64(vec) = std::mem::replace(&mut column.data, ColumnData::empty(dtype)) {
+            column.data = match dtype {
+                DType::Duration => ColumnData::Duration(vec),
+                _ => ColumnData::Datetime(vec)
+            };
+        }
+
+        column
+    }
+
+    fn micros(&self, dtype: DType) -> Result<Vec<Option<i64>>, DataLoaderError> {
+        if self.dtype() != dtype {
+            return Err(DataLoaderError::GenericError(format!("Expected a {} column but '{}' is {}", dtype, self.label.as_deref().unwrap_or_default(), self.dtype())));
+        }
+
+        self.to_options::<i64>()
+    }
+
+    fn feature_label(&self, feature: &str) -> Option<String> {
+        Some(match &self.label {
+            Some(label) => format!("{}_{}", label, feature),
+            None => feature.to_string()
+        })
+    }
+
+    fn datetime_feature<T: ColumnType>(&self, feature: &str, extract: impl Fn(NaiveDateTime) -> T) -> Result<DataVector, DataLoaderError> {
+        let values: Vec<Option<T>> = self.to_datetimes()?.into_iter().map(|v| v.map(&extract)).collect();
+        Ok(Self::from_nullable(self.feature_label(feature), values))
+    }
+}
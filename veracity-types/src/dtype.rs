@@ -0,0 +1,49 @@
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DType {
+    Bool,
+    I32,
+    I64,
+    F32,
+    F64,
+    Utf8,
+    Categorical,
+    Datetime,
+    Duration
+}
+
+impl DType {
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, DType::I32 | DType::I64 | DType::F32 | DType::F64)
+    }
+
+    pub fn is_integer(&self) -> bool {
+        matches!(self, DType::I32 | DType::I64)
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(self, DType::F32 | DType::F64)
+    }
+
+    pub fn is_temporal(&self) -> bool {
+        matches!(self, DType::Datetime | DType::Duration)
+    }
+}
+
+impl fmt::Display for DType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DType::Bool => write!(f, "bool"),
+            DType::I32 => write!(f, "i32"),
+            DType::I64 => write!(f, "i64"),
+            DType::F32 => write!(f, "f32"),
+            DType::F64 => write!(f, "f64"),
+            DType::Utf8 => write!(f, "utf8"),
+            DType::Categorical => write!(f, "categorical"),
+            DType::Datetime => write!(f, "datetime"),
+            DType::Duration => write!(f, "duration")
+        }
+    }
+}
@@ -1 +1,2 @@
+pub mod dtype;
 pub mod errors;
\ No newline at end of file